@@ -0,0 +1,490 @@
+//! A probabilistic model of the hidden hands of all other bots.
+//!
+//! [Belief] keeps a probability for every hand each other bot at the table
+//! could be holding.
+//! It starts with how likely a hand is to be dealt from the cards you can't
+//! see and updates that with everything that happens in the game:
+//! - Cards that end up in the discard pile can't be in anyones hand
+//! - Actions and counters make the claimed card more likely
+//! - A lost challenge proves a bot didn't have the card it claimed
+//! - A won challenge or an exchange with the deck gives a bot new cards
+//! - A lost influence shows which card a bot had
+//!
+//! Build it from the [Context] you get in each call of your bot or keep one
+//! around and [Belief::update] it:
+//!
+//! ```rust
+//! use coup::{
+//!     belief::Belief,
+//!     bot::{BotInterface, Context},
+//!     Action, Card,
+//! };
+//!
+//! pub struct MyBot;
+//!
+//! impl BotInterface for MyBot {
+//!     fn get_name(&self) -> String {
+//!         String::from("MyBot")
+//!     }
+//!
+//!     fn on_challenge_action_round(
+//!         &mut self,
+//!         action: &Action,
+//!         by: String,
+//!         context: &Context,
+//!     ) -> bool {
+//!         let belief = Belief::new(context);
+//!         matches!(action, Action::Tax)
+//!             && belief.probability_has(&by, Card::Duke) < 0.2
+//!     }
+//! }
+//! ```
+
+use crate::{bot::Context, Card, History, Reformation};
+
+/// All cards in the order [Belief] lists them.
+/// Only five of them are in the deck, see [Reformation::cards].
+pub const CARDS: [Card; 6] = [
+	Card::Duke,
+	Card::Assassin,
+	Card::Captain,
+	Card::Contessa,
+	Card::Ambassador,
+	Card::Inquisitor,
+];
+
+/// How likely a bot is to claim a card it doesn't have compared to one it has.
+pub const DEFAULT_BLUFF_RATE: f64 = 0.3;
+
+/// A hand of cards sorted in the order of [CARDS].
+pub type Hand = Vec<Card>;
+
+fn card_index(card: Card) -> usize {
+	CARDS.iter().position(|c| *c == card).unwrap()
+}
+
+fn n_choose_k(n: usize, k: usize) -> f64 {
+	if k > n {
+		return 0.0;
+	}
+	(0..k).fold(1.0, |result, i| result * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Every hand with `size` cards, each in the order of [CARDS].
+fn all_hands(size: usize) -> Vec<Hand> {
+	match size {
+		0 => vec![vec![]],
+		_ => all_hands(size - 1)
+			.into_iter()
+			.flat_map(|hand| {
+				let from = hand.last().map_or(0, |card| card_index(*card));
+				CARDS[from..].iter().map(move |card| {
+					let mut hand = hand.clone();
+					hand.push(*card);
+					hand
+				})
+			})
+			.collect(),
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Opponent {
+	name: String,
+	/// How well each hand explains what this bot did so far
+	/// (the likelihood, the prior comes from the cards we can't see)
+	hands: Vec<(Hand, f64)>,
+}
+
+impl Opponent {
+	fn new(name: String, size: usize) -> Self {
+		Self {
+			name,
+			hands: all_hands(size).into_iter().map(|hand| (hand, 1.0)).collect(),
+		}
+	}
+
+	fn size(&self) -> usize {
+		self.hands.first().map_or(0, |(hand, _)| hand.len())
+	}
+
+	/// Multiply each hand with how likely it is to do what we just saw.
+	fn weigh(&mut self, likelihood: impl Fn(&Hand) -> f64) {
+		self
+			.hands
+			.iter_mut()
+			.for_each(|(hand, weight)| *weight *= likelihood(hand));
+		// Nothing fits anymore (e.g. the bot was bluffing a lot) so start over
+		if self.hands.iter().all(|(_, weight)| *weight == 0.0) {
+			self.forget();
+		}
+	}
+
+	/// Forget everything we learned about this hand.
+	fn forget(&mut self) {
+		let size = self.size();
+		*self = Self::new(self.name.clone(), size);
+	}
+
+	/// The bot lost `card` and keeps the rest of its hand.
+	fn lose(&mut self, card: Card) {
+		let size = self.size().saturating_sub(1);
+		let mut hands = Opponent::new(self.name.clone(), size).hands;
+		hands.iter_mut().for_each(|(rest, weight)| {
+			let mut hand = rest.clone();
+			hand.push(card);
+			hand.sort_by_key(|card| card_index(*card));
+			*weight = self
+				.hands
+				.iter()
+				.find(|(old, _)| *old == hand)
+				.map_or(0.0, |(_, old_weight)| *old_weight);
+		});
+		self.hands = hands;
+		if self.hands.iter().all(|(_, weight)| *weight == 0.0) {
+			self.forget();
+		}
+	}
+
+	/// The bot showed `card`, shuffled it back and drew a new card so we only
+	/// keep what we know about the other cards.
+	fn redraw(&mut self, card: Card) {
+		let size = self.size();
+		self.lose(card);
+		let rest = std::mem::take(&mut self.hands);
+		*self = Self::new(self.name.clone(), size);
+		self.hands.iter_mut().for_each(|(hand, weight)| {
+			*weight = hand
+				.iter()
+				.enumerate()
+				.filter(|(i, _)| *i == 0 || hand[*i - 1] != hand[*i])
+				.filter_map(|(i, _)| {
+					let mut others = hand.clone();
+					others.remove(i);
+					rest.iter().find(|(old, _)| *old == others).map(|(_, w)| *w)
+				})
+				.fold(0.0, f64::max);
+		});
+	}
+}
+
+/// The probability of every hand each other bot could have.
+/// See the [module](crate::belief) for how it's updated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Belief {
+	name: String,
+	cards: Vec<Card>,
+	discard_pile: Vec<Card>,
+	opponents: Vec<Opponent>,
+	reformation: Reformation,
+	copies: usize,
+	bluff_rate: f64,
+	seen: usize,
+	// Who claimed what and whether they claimed to have or not to have it
+	last_claim: Option<(String, Vec<Card>, bool)>,
+}
+
+impl Belief {
+	/// Build a belief from everything that happened in the game so far.
+	pub fn new(context: &Context) -> Self {
+		Self::with_bluff_rate(context, DEFAULT_BLUFF_RATE)
+	}
+
+	/// Like [Belief::new] but with your own guess of how often bots bluff.
+	/// A bluff rate of `1.0` ignores claims completely, `0.0` trusts them
+	/// blindly.
+	pub fn with_bluff_rate(context: &Context, bluff_rate: f64) -> Self {
+		let mut belief = Self {
+			name: context.name.clone(),
+			cards: vec![],
+			discard_pile: vec![],
+			opponents: context
+				.playing_bots
+				.iter()
+				.filter(|bot| bot.name != context.name)
+				.map(|bot| Opponent::new(bot.name.clone(), 2))
+				.collect(),
+			reformation: context.config.reformation,
+			copies: context.copies,
+			bluff_rate: bluff_rate.clamp(0.0, 1.0),
+			seen: 0,
+			last_claim: None,
+		};
+		belief.update(context);
+		belief
+	}
+
+	/// Catch up with everything that happened since the last update.
+	/// Use this if you keep the belief around between calls within a game.
+	pub fn update(&mut self, context: &Context) {
+		self.cards.clone_from(&context.cards);
+		self.discard_pile.clone_from(&context.discard_pile);
+
+		for event in context.history.iter().skip(self.seen) {
+			self.observe(event);
+		}
+		self.seen = context.history.len();
+
+		// Bots that are out don't have a hand to guess anymore
+		self.opponents.retain(|opponent| {
+			context
+				.playing_bots
+				.iter()
+				.any(|bot| bot.name == opponent.name && bot.cards > 0)
+		});
+		// Anything else we missed about the size of a hand
+		for opponent in self.opponents.iter_mut() {
+			let size = context
+				.playing_bots
+				.iter()
+				.find(|bot| bot.name == opponent.name)
+				.map_or(0, |bot| bot.cards as usize);
+			if size != opponent.size() {
+				*opponent = Opponent::new(opponent.name.clone(), size);
+			}
+		}
+	}
+
+	fn opponent(&mut self, name: &str) -> Option<&mut Opponent> {
+		self.opponents.iter_mut().find(|opponent| opponent.name == name)
+	}
+
+	fn claim(&mut self, by: &str, cards: Vec<Card>, has: bool) {
+		let bluff_rate = self.bluff_rate;
+		if let Some(opponent) = self.opponent(by) {
+			opponent.weigh(|hand| {
+				if cards.iter().any(|card| hand.contains(card)) == has {
+					1.0
+				} else {
+					bluff_rate
+				}
+			});
+		}
+		self.last_claim = Some((by.to_string(), cards, has));
+	}
+
+	fn observe(&mut self, event: &History) {
+		match event {
+			History::ActionAssassination { by, .. } => {
+				self.claim(by, vec![Card::Assassin], true)
+			},
+			History::ActionSwapping { by } => {
+				self.claim(by, vec![self.reformation.exchange_card()], true)
+			},
+			History::ActionStealing { by, .. } => {
+				self.claim(by, vec![Card::Captain], true)
+			},
+			History::ActionTax { by } => self.claim(by, vec![Card::Duke], true),
+			History::ActionExamine { by, .. } => {
+				self.claim(by, vec![Card::Inquisitor], true)
+			},
+			History::ActionEmbezzlement { by } => {
+				self.claim(by, vec![Card::Duke], false)
+			},
+			History::CounterAssassination { by, .. } => {
+				self.claim(by, vec![Card::Contessa], true)
+			},
+			History::CounterForeignAid { by, .. } => {
+				self.claim(by, vec![Card::Duke], true)
+			},
+			History::CounterStealing { by, .. } => self.claim(
+				by,
+				vec![Card::Captain, self.reformation.exchange_card()],
+				true,
+			),
+			// Caught bluffing so the claim was the wrong way around
+			History::ChallengeSuccessful { target, .. }
+			| History::CounterChallengeSuccessful { target, .. } => {
+				if let Some((by, cards, has)) = self.last_claim.clone() {
+					if by == *target {
+						if let Some(opponent) = self.opponent(target) {
+							opponent.weigh(|hand| {
+								if cards.iter().any(|card| hand.contains(card)) == has {
+									0.0
+								} else {
+									1.0
+								}
+							});
+						}
+					}
+				}
+			},
+			History::RevealCard { by, card } => {
+				if let Some(opponent) = self.opponent(by) {
+					opponent.redraw(*card);
+				}
+			},
+			History::LoseInfluence { by, card } => {
+				if let Some(opponent) = self.opponent(by) {
+					opponent.lose(*card);
+				}
+			},
+			// A whole new hand picked from their old one and cards from the deck
+			History::ExchangeCards { by }
+			| History::ForceExchange { target: by, .. } => {
+				if let Some(opponent) = self.opponent(by) {
+					opponent.forget();
+				}
+			},
+			_ => {},
+		}
+	}
+
+	/// How many copies of `card` are neither in your hand nor in the discard pile.
+	pub fn remaining_copies(&self, card: Card) -> usize {
+		if !self.reformation.cards().contains(&card) {
+			return 0;
+		}
+		let visible = self
+			.cards
+			.iter()
+			.chain(self.discard_pile.iter())
+			.filter(|c| **c == card)
+			.count();
+		self.copies.saturating_sub(visible)
+	}
+
+	/// How likely a hand is to be dealt from the cards you can't see.
+	fn prior(&self, hand: &Hand) -> f64 {
+		let hidden =
+			CARDS.iter().map(|card| self.remaining_copies(*card)).sum::<usize>();
+		let ways = CARDS
+			.iter()
+			.map(|card| {
+				let count = hand.iter().filter(|c| *c == card).count();
+				n_choose_k(self.remaining_copies(*card), count)
+			})
+			.product::<f64>();
+		ways / n_choose_k(hidden, hand.len())
+	}
+
+	/// The names of all other bots still in the game.
+	pub fn opponents(&self) -> Vec<String> {
+		self.opponents.iter().map(|opponent| opponent.name.clone()).collect()
+	}
+
+	/// Every hand the bot `name` could have with its probability, most likely
+	/// first.
+	/// Returns an empty list for bots that are out or unknown.
+	pub fn hands(&self, name: &str) -> Vec<(Hand, f64)> {
+		let Some(opponent) = self.opponents.iter().find(|o| o.name == name) else {
+			return vec![];
+		};
+
+		let mut hands = opponent
+			.hands
+			.iter()
+			.map(|(hand, weight)| (hand.clone(), self.prior(hand) * weight))
+			.filter(|(_, probability)| *probability > 0.0)
+			.collect::<Vec<(Hand, f64)>>();
+		let total = hands.iter().map(|(_, p)| p).sum::<f64>();
+		if total > 0.0 {
+			hands.iter_mut().for_each(|(_, p)| *p /= total);
+		}
+		hands.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+		hands
+	}
+
+	/// The probability that the bot `name` has at least one `card`.
+	pub fn probability_has(&self, name: &str, card: Card) -> f64 {
+		self
+			.hands(name)
+			.iter()
+			.filter(|(hand, _)| hand.contains(&card))
+			.map(|(_, probability)| probability)
+			.sum()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{bot::OtherBot, GameConfig};
+
+	fn context(history: Vec<History>, discard_pile: Vec<Card>) -> Context {
+		let bot = |name: &str, cards: u8| OtherBot {
+			name: name.to_string(),
+			coins: 2,
+			cards,
+			allegiance: None,
+		};
+		Context {
+			name: String::from("me"),
+			cards: vec![Card::Duke, Card::Captain],
+			coins: 2,
+			playing_bots: vec![bot("me", 2), bot("a", 2), bot("b", 1)],
+			discard_pile,
+			history,
+			moves: 0,
+			score: vec![],
+			config: GameConfig::default(),
+			treasury: 50,
+			treasury_reserve: 0,
+			copies: 3,
+		}
+	}
+
+	#[test]
+	fn test_belief() {
+		let belief = Belief::new(&context(vec![], vec![Card::Duke]));
+		assert_eq!(belief.opponents(), vec!["a", "b"]);
+		assert_eq!(belief.remaining_copies(Card::Duke), 1);
+		assert_eq!(belief.hands("a").len(), 14);
+		assert_eq!(belief.hands("b").len(), 5);
+		assert!(
+			(belief.hands("a").iter().map(|(_, p)| p).sum::<f64>() - 1.0).abs()
+				< 1e-9
+		);
+		// 1 Duke out of 12 hidden cards
+		assert!(
+			(belief.probability_has("b", Card::Duke) - 1.0 / 12.0).abs() < 1e-9
+		);
+		assert!(
+			belief.probability_has("a", Card::Duke)
+				< belief.probability_has("a", Card::Contessa)
+		);
+		assert!(belief.hands("nobody").is_empty());
+	}
+
+	#[test]
+	fn test_belief_history() {
+		let by = |name: &str| name.to_string();
+		let mut history = vec![History::ActionTax { by: by("a") }];
+		let before = Belief::new(&context(vec![], vec![]));
+		let claimed = Belief::new(&context(history.clone(), vec![]));
+		assert!(
+			claimed.probability_has("a", Card::Duke)
+				> before.probability_has("a", Card::Duke)
+		);
+
+		// a was bluffing
+		history.push(History::ChallengeDuke {
+			by: by("me"),
+			target: by("a"),
+		});
+		history.push(History::ChallengeSuccessful {
+			by: by("me"),
+			target: by("a"),
+		});
+		history.push(History::LoseInfluence {
+			by: by("a"),
+			card: Card::Captain,
+		});
+		let mut ctx = context(history, vec![Card::Captain]);
+		ctx.playing_bots[1].cards = 1;
+		let caught = Belief::new(&ctx);
+		assert_eq!(caught.probability_has("a", Card::Duke), 0.0);
+		assert_eq!(caught.hands("a").len(), 4);
+
+		// keeping it around and updating it gives the same answer
+		let mut kept = Belief::new(&context(vec![], vec![]));
+		kept.update(&ctx);
+		assert_eq!(kept.hands("a"), caught.hands("a"));
+
+		// a new hand after swapping
+		ctx.history.push(History::ExchangeCards { by: by("a") });
+		let mut swapped = Belief::new(&ctx);
+		swapped.update(&ctx);
+		assert!(swapped.probability_has("a", Card::Duke) > 0.0);
+	}
+}
@@ -14,7 +14,11 @@
 //! }
 //! ```
 
-use crate::{Action, Card, History, Score};
+use std::sync::Arc;
+
+use crate::{
+	game_log::Seat, Action, Allegiance, Card, GameConfig, History, Score,
+};
 
 /// A bot struct can be used to implement the [BotInterface] trait
 #[derive(Debug, Clone, Copy)]
@@ -29,6 +33,9 @@ pub struct OtherBot {
 	pub coins: u8,
 	/// The amount of [Card] this bot still have
 	pub cards: u8,
+	/// The faction of this bot when playing with the factions of the
+	/// [crate::Reformation] rules
+	pub allegiance: Option<Allegiance>,
 }
 
 /// The context struct is what is passed into each of the [BotInterface] methods
@@ -49,8 +56,54 @@ pub struct Context {
 	pub discard_pile: Vec<Card>,
 	/// A list of each event that has happened in this game so far
 	pub history: Vec<History>,
+	/// The number of the current move, after [GameConfig::max_moves] the game
+	/// ends in a stalemate
+	pub moves: usize,
 	/// The current score of the game
 	pub score: Score,
+	/// The numbers and rules this game is played with
+	pub config: GameConfig,
+	/// The coins left in the treasury that [Action::Income], [Action::ForeignAid]
+	/// and [Action::Tax] are paid out of
+	pub treasury: u8,
+	/// The coins in the Treasury Reserve paid by [Action::Conversion] waiting
+	/// for someone to play [Action::Embezzlement]
+	pub treasury_reserve: u8,
+	/// How many copies of each card are in the deck of this game, 3 or 5 for
+	/// tables of more than 6 bots
+	pub copies: usize,
+}
+
+impl Context {
+	/// Whether you can coup, assassinate, steal from or examine the bot `name`.
+	/// With the factions of the [crate::Reformation] rules you can't target your
+	/// own faction unless everyone is on the same side.
+	pub fn can_target(&self, name: &str) -> bool {
+		let allegiance = |name: &str| {
+			self
+				.playing_bots
+				.iter()
+				.find(|bot| bot.name == name)
+				.map(|bot| bot.allegiance)
+		};
+		let mine = allegiance(&self.name).flatten();
+
+		name != self.name
+			&& match allegiance(name) {
+				None => false,
+				Some(theirs) => {
+					mine.is_none()
+						|| theirs != mine
+						|| self.playing_bots.iter().all(|bot| bot.allegiance == mine)
+				},
+			}
+	}
+
+	/// All bots you can coup, assassinate, steal from or examine.
+	/// See [Context::can_target].
+	pub fn targets(&self) -> Vec<&OtherBot> {
+		self.playing_bots.iter().filter(|bot| self.can_target(&bot.name)).collect()
+	}
 }
 
 /// The BotInterface trait is what drives your bot.
@@ -65,23 +118,18 @@ pub trait BotInterface {
 	/// Called when it's your turn to decide what to do.
 	///
 	/// The static implementation always plays [Action::Income].
-	fn on_turn(&self, _context: &Context) -> Action {
+	fn on_turn(&mut self, _context: &Context) -> Action {
 		Action::Income
 	}
 
-	/// Called when you have equal to or more than 10 coins and must coup.
+	/// Called when you have equal to or more than 10 coins (the
+	/// [GameConfig::auto_coup_coins]) and must coup.
 	/// You can use this method internally as well when you decide to coup on
 	/// your own.
 	///
-	/// The static implementation coups the first bot it finds that isn't itself.
-	fn on_auto_coup(&self, context: &Context) -> String {
-		context
-			.playing_bots
-			.iter()
-			.find(|bot| bot.name != context.name)
-			.unwrap()
-			.name
-			.clone()
+	/// The static implementation coups the first bot it can target.
+	fn on_auto_coup(&mut self, context: &Context) -> String {
+		context.targets()[0].name.clone()
 	}
 
 	/// Called when another bot played an action and everyone gets to decide
@@ -92,10 +140,12 @@ pub trait BotInterface {
 	/// - [Action::Swapping]
 	/// - [Action::Stealing]
 	/// - [Action::Tax]
+	/// - [Action::Embezzlement]
+	/// - [Action::Examine]
 	///
 	/// The static implementation never challenges.
 	fn on_challenge_action_round(
-		&self,
+		&mut self,
 		_action: &Action,
 		_by: String,
 		_context: &Context,
@@ -113,7 +163,7 @@ pub trait BotInterface {
 	///
 	/// The static implementation never counters.
 	fn on_counter(
-		&self,
+		&mut self,
 		_action: &Action,
 		_by: String,
 		_context: &Context,
@@ -131,7 +181,7 @@ pub trait BotInterface {
 	///
 	/// The static implementation never counter-challenges.
 	fn on_challenge_counter_round(
-		&self,
+		&mut self,
 		_action: &Action,
 		_by: String,
 		_context: &Context,
@@ -146,17 +196,110 @@ pub trait BotInterface {
 	///
 	/// The static implementation gives back the cards it got from the deck.
 	fn on_swapping_cards(
-		&self,
+		&mut self,
 		new_cards: [Card; 2],
 		_context: &Context,
 	) -> [Card; 2] {
 		new_cards
 	}
 
+	/// Called when you played your inquisitor to swap and now need to decide
+	/// whether you want to keep the card you drew.
+	/// Return the card you don't want anymore. It will be shuffled back into the
+	/// deck.
+	///
+	/// The static implementation gives back the card it got from the deck.
+	fn on_exchanging_card(&mut self, new_card: Card, _context: &Context) -> Card {
+		new_card
+	}
+
+	/// Called when another bot examines you with the inquisitor.
+	/// Return the card you show them.
+	///
+	/// The static implementation shows its first card.
+	fn on_examine(&mut self, _by: String, context: &Context) -> Card {
+		context.cards[0]
+	}
+
+	/// Called when you examined another bot with the inquisitor and it showed
+	/// you a card.
+	/// Return true to make it exchange that card with a card from the deck.
+	///
+	/// The static implementation lets it keep the card.
+	fn on_examined_card(
+		&mut self,
+		_card: Card,
+		_target: String,
+		_context: &Context,
+	) -> bool {
+		false
+	}
+
+	/// Called before the game starts when the [GameConfig::dealt_cards] deal you
+	/// more than two cards, e.g. in the two-player variant.
+	/// Return the two cards you want to keep. The rest go back into the deck.
+	/// If you return cards you weren't dealt you keep the first two.
+	/// Your cards in the context stay empty until you picked.
+	///
+	/// The static implementation keeps the first two cards.
+	fn on_starting_cards(
+		&mut self,
+		dealt_cards: Vec<Card>,
+		_context: &Context,
+	) -> [Card; 2] {
+		[dealt_cards[0], dealt_cards[1]]
+	}
+
 	/// Called when you lost a card and now must decide which one you want to lose.
 	///
 	/// The static implementation discards the first card it finds.
-	fn on_card_loss(&self, context: &Context) -> Card {
+	fn on_card_loss(&mut self, context: &Context) -> Card {
 		context.cards.clone().pop().unwrap()
 	}
+
+	/// Called for each bot at the table once the cards are dealt and before the
+	/// first move of a game.
+	/// Use this to reset anything you remember about the last game.
+	///
+	/// The static implementation does nothing.
+	fn on_game_start(&mut self, _context: &Context) {}
+
+	/// Called for each bot at the table when the game has ended with the names
+	/// of the winners and the cards each bot was left with.
+	///
+	/// The static implementation does nothing.
+	fn on_game_end(
+		&mut self,
+		_winners: &[String],
+		_hands: &[Seat],
+		_context: &Context,
+	) {
+	}
+
+	/// Called for each bot at the table, including the ones that are already
+	/// out, every time something is added to the [History].
+	///
+	/// The static implementation does nothing.
+	fn on_event(&mut self, _event: &History) {}
+}
+
+/// A factory builds a new instance of a bot.
+/// Factories can be shared between threads so each thread can build its own
+/// bots even when the bots themselves can't be sent between threads.
+pub type BotFactory = Arc<dyn Fn() -> Box<dyn BotInterface> + Send + Sync>;
+
+/// Create a [BotFactory] from a function or closure that returns a bot.
+///
+/// ```rust
+/// use coup::{bot::factory, bots::StaticBot};
+///
+/// let static_bot = factory(|| StaticBot);
+/// let bot = static_bot();
+/// ```
+pub fn factory<B, F>(make: F) -> BotFactory
+where
+	B: BotInterface + 'static,
+	F: Fn() -> B + Send + Sync + 'static,
+{
+	Arc::new(move || Box::new(make()))
 }
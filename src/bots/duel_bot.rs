@@ -3,8 +3,6 @@ use crate::{
     Action, Card, History,
 };
 
-use std::cell::RefCell;
-
 const N_CARDS: usize = 5;
 
 #[derive(Default)]
@@ -17,18 +15,17 @@ struct Memory {
     assassination_blocked_streak: u32,
 }
 
-thread_local! {
-    static MEMORY: RefCell<Memory> = RefCell::new(Memory::default());
+#[derive(Default)]
+pub struct DuelBot {
+    memory: Memory,
 }
 
-pub struct DuelBot;
-
 fn card_idx(c: Card) -> usize {
     match c {
         Card::Duke => 0,
         Card::Assassin => 1,
         Card::Captain => 2,
-        Card::Ambassador => 3,
+        Card::Ambassador | Card::Inquisitor => 3,
         Card::Contessa => 4,
     }
 }
@@ -40,106 +37,107 @@ fn sigmoid(x: f64) -> f64 {
 impl DuelBot {
     // Memory and history
 
-    fn update_from_history(context: &Context) {
-        // Reset memory at the start of a new game
-        if context.history.is_empty() {
-            MEMORY.with(|m| *m.borrow_mut() = Memory::default());
+    fn update_from_history(&mut self, context: &Context) {
+        let mem = &mut self.memory;
+
+        if mem.seen_history_len >= context.history.len() {
             return;
         }
 
-        MEMORY.with(|m| {
-            let mut mem = m.borrow_mut();
-
-            if mem.seen_history_len >= context.history.len() {
-                return;
-            }
-
-            // 1v1 opponent name
-            let opp_name = context
-                .playing_bots
-                .iter()
-                .find(|b| b.name != context.name)
-                .unwrap()
-                .name
-                .clone();
-
-            for h in &context.history[mem.seen_history_len..] {
-                match h {
-                    // Opponent claims
-                    History::ActionTax { by } if *by == opp_name => {
-                        mem.opp_claims[card_idx(Card::Duke)] += 1;
-                    }
-                    History::ActionAssassination { by, .. } if *by == opp_name => {
-                        mem.opp_claims[card_idx(Card::Assassin)] += 1;
-                    }
-                    History::ActionStealing { by, .. } if *by == opp_name => {
-                        mem.opp_claims[card_idx(Card::Captain)] += 1;
-                    }
-                    History::ActionSwapping { by } if *by == opp_name => {
-                        mem.opp_claims[card_idx(Card::Ambassador)] += 1;
-                    }
+        // 1v1 opponent name
+        let opp_name = context
+            .playing_bots
+            .iter()
+            .find(|b| b.name != context.name)
+            .unwrap()
+            .name
+            .clone();
+
+        for h in &context.history[mem.seen_history_len..] {
+            match h {
+                // Opponent claims
+                History::ActionTax { by } if *by == opp_name => {
+                    mem.opp_claims[card_idx(Card::Duke)] += 1;
+                }
+                History::ActionAssassination { by, .. } if *by == opp_name => {
+                    mem.opp_claims[card_idx(Card::Assassin)] += 1;
+                }
+                History::ActionStealing { by, .. } if *by == opp_name => {
+                    mem.opp_claims[card_idx(Card::Captain)] += 1;
+                }
+                History::ActionSwapping { by } if *by == opp_name => {
+                    mem.opp_claims[card_idx(context.config.reformation.exchange_card())] += 1;
+                }
 
-                    // Opponents claims for counters (blocks)
-                    History::CounterForeignAid { by, .. } if *by == opp_name => {
-                        mem.opp_claims[card_idx(Card::Duke)] += 1;
-                    }
-                    History::CounterAssassination { by, .. } if *by == opp_name => {
-                        mem.opp_claims[card_idx(Card::Contessa)] += 1;
-
-                        // If we had an assassination pending, it was blocked
-                        if mem.my_assassination_pending {
-                            mem.assassination_blocked_streak =
-                                mem.assassination_blocked_streak.saturating_add(1);
-                            mem.my_assassination_pending = false;
-                        }
-                    }
-                    History::CounterStealing { by, .. } if *by == opp_name => {
-                        // Steal block could be Captain OR Ambassador; count both as soft claims
-                        mem.opp_claims[card_idx(Card::Captain)] += 1;
-                        mem.opp_claims[card_idx(Card::Ambassador)] += 1;
-                    }
+                // Opponents claims for counters (blocks)
+                History::CounterForeignAid { by, .. } if *by == opp_name => {
+                    mem.opp_claims[card_idx(Card::Duke)] += 1;
+                }
+                History::CounterAssassination { by, .. } if *by == opp_name => {
+                    mem.opp_claims[card_idx(Card::Contessa)] += 1;
 
-                    // ----- Our actions: track assassination pending + reset streak on other actions -----
-                    History::ActionAssassination { by, .. } if *by == context.name => {
-                        mem.my_assassination_pending = true;
-                    }
-                    History::ActionTax { by } if *by == context.name => {
-                        mem.my_assassination_pending = false;
-                        mem.assassination_blocked_streak = 0;
-                    }
-                    History::ActionStealing { by, .. } if *by == context.name => {
-                        mem.my_assassination_pending = false;
-                        mem.assassination_blocked_streak = 0;
-                    }
-                    History::ActionSwapping { by } if *by == context.name => {
+                    // If we had an assassination pending, it was blocked
+                    if mem.my_assassination_pending {
+                        mem.assassination_blocked_streak =
+                            mem.assassination_blocked_streak.saturating_add(1);
                         mem.my_assassination_pending = false;
-                        mem.assassination_blocked_streak = 0;
                     }
+                }
+                History::CounterStealing { by, .. } if *by == opp_name => {
+                    // Steal block could be Captain OR the exchange card; count both as soft claims
+                    mem.opp_claims[card_idx(Card::Captain)] += 1;
+                    mem.opp_claims[card_idx(context.config.reformation.exchange_card())] += 1;
+                }
+
+                // ----- Our actions: track assassination pending + reset streak on other actions -----
+                History::ActionAssassination { by, .. } if *by == context.name => {
+                    mem.my_assassination_pending = true;
+                }
+                History::ActionTax { by } if *by == context.name => {
+                    mem.my_assassination_pending = false;
+                    mem.assassination_blocked_streak = 0;
+                }
+                History::ActionStealing { by, .. } if *by == context.name => {
+                    mem.my_assassination_pending = false;
+                    mem.assassination_blocked_streak = 0;
+                }
+                History::ActionSwapping { by } if *by == context.name => {
+                    mem.my_assassination_pending = false;
+                    mem.assassination_blocked_streak = 0;
+                }
 
-                    _ => {}
+                // Revealed cards go back into the deck and exchanged hands are
+                // unknown again, so earlier claims no longer tell us anything
+                History::RevealCard { by, card } if *by == opp_name => {
+                    mem.opp_claims[card_idx(*card)] = 0;
+                }
+                History::ExchangeCards { by } if *by == opp_name => {
+                    mem.opp_claims = [0; N_CARDS];
                 }
+
+                _ => {}
             }
+        }
 
-            mem.seen_history_len = context.history.len();
-        });
+        mem.seen_history_len = context.history.len();
     }
 
-    fn opp_claims(card: Card) -> u32 {
-        MEMORY.with(|m| m.borrow().opp_claims[card_idx(card)])
+    fn opp_claims(&self, card: Card) -> u32 {
+        self.memory.opp_claims[card_idx(card)]
     }
 
-    fn assassination_blocked_streak() -> u32 {
-        MEMORY.with(|m| m.borrow().assassination_blocked_streak)
+    fn assassination_blocked_streak(&self) -> u32 {
+        self.memory.assassination_blocked_streak
     }
 
-    fn set_assassination_pending(pending: bool) {
-        MEMORY.with(|m| m.borrow_mut().my_assassination_pending = pending);
+    fn set_assassination_pending(&mut self, pending: bool) {
+        self.memory.my_assassination_pending = pending;
     }
 
     // -------- Card knowledge tables --------
 
-    fn opponent<'a>(context: &'a Context) -> &'a crate::bot::OtherBot {
-        context.playing_bots.iter().find(|b| b.name != context.name).unwrap()
+    fn opponent(context: &Context) -> &crate::bot::OtherBot {
+        context.targets()[0]
     }
 
     fn visible_count(context: &Context, card: Card) -> usize {
@@ -149,12 +147,12 @@ impl DuelBot {
     }
 
     fn remaining_copies(context: &Context, card: Card) -> i32 {
-        (3 - Self::visible_count(context, card) as i32).max(0)
+        (context.copies as i32 - Self::visible_count(context, card) as i32).max(0)
     }
 
     fn hidden_total(context: &Context) -> i32 {
         let visible = context.cards.len() as i32 + context.discard_pile.len() as i32;
-        (15 - visible).max(0)
+        (5 * context.copies as i32 - visible).max(0)
     }
 
     // -------- Hypergeometric probability: P(opponent has >=1 of card) --------
@@ -173,7 +171,7 @@ impl DuelBot {
         num / den
     }
 
-    fn p_opponent_has(context: &Context, opp_cards: i32, card: Card) -> f64 {
+    fn p_opponent_has(&self, context: &Context, opp_cards: i32, card: Card) -> f64 {
         let n = Self::hidden_total(context);
         let k = Self::remaining_copies(context, card);
 
@@ -185,7 +183,7 @@ impl DuelBot {
         let base = 1.0 - (Self::n_choose_k(n - k, h) / Self::n_choose_k(n, h));
 
         // Credibility boost based on repeated claims
-        let claims = Self::opp_claims(card) as f64;
+        let claims = self.opp_claims(card) as f64;
         let mut odds = base / (1.0 - base + 1e-9);
         odds *= (0.35 * claims).exp();
 
@@ -196,19 +194,19 @@ impl DuelBot {
     //
     // Returns p_challenge. Then p_no_challenge = 1 - p_challenge.
     // This is an opponent model: "How likely are they to challenge this claim?"
-    fn p_opponent_challenges_claim(context: &Context, claimed_role: Card, stake: f64) -> f64 {
+    fn p_opponent_challenges_claim(&self, context: &Context, claimed_role: Card, stake: f64) -> f64 {
         let opp = Self::opponent(context);
 
-        let remaining = Self::remaining_copies(context, claimed_role).max(0) as f64; // 0..3
+        let remaining = Self::remaining_copies(context, claimed_role).max(0) as f64; // 0..copies
         if remaining <= 0.0 {
             return 0.999;
         }
 
         // Scarcity drives challenges
-        let scarcity = 1.0 - (remaining / 3.0); // 0..1
+        let scarcity = 1.0 - (remaining / context.copies as f64); // 0..1
 
         // If it's plausible THEY have the card, they may feel safer and challenge slightly less
-        let p_opp_has_role = Self::p_opponent_has(context, opp.cards as i32, claimed_role);
+        let p_opp_has_role = self.p_opponent_has(context, opp.cards as i32, claimed_role);
         let cover_effect = (p_opp_has_role - 0.5) * -0.4;
 
         // If they are ahead, they can afford challenge risk more
@@ -229,12 +227,12 @@ impl DuelBot {
     // EV-based bluff: compare "claim role action" vs safe fallback.
     // reward_delta: how much better than Income this is if not challenged.
     // stake: how important / conspicuous the claim is.
-    fn bluff_ev_ok(context: &Context, role: Card, stake: f64, reward_delta: f64) -> bool {
+    fn bluff_ev_ok(&self, context: &Context, role: Card, stake: f64, reward_delta: f64) -> bool {
         if Self::remaining_copies(context, role) == 0 {
             return false;
         }
 
-        let p_chal = Self::p_opponent_challenges_claim(context, role, stake);
+        let p_chal = self.p_opponent_challenges_claim(context, role, stake);
         let p_no = 1.0 - p_chal;
 
         let risk_loss = if context.cards.len() <= 1 { 2.0 } else { 1.0 }; // losing last influence is huge
@@ -249,7 +247,7 @@ impl DuelBot {
         // "lose next turn" in 1v1 typically means: we have 1 influence left,
         // opponent can coup on their next turn (coins >= 7).
         let opp = Self::opponent(context);
-        context.cards.len() <= 1 && opp.coins >= 7
+        context.cards.len() <= 1 && opp.coins >= context.config.coup_cost
     }
 
     fn opponent_coup_threat_next_turn(context: &Context) -> bool {
@@ -260,16 +258,16 @@ impl DuelBot {
     }
 
     // Should we attempt assassination this turn? (prevents "blocked forever")
-    fn should_attempt_assassination(context: &Context) -> bool {
-        if context.coins < 3 {
+    fn should_attempt_assassination(&self, context: &Context) -> bool {
+        if context.coins < context.config.assassination_cost {
             return false;
         }
 
         let opp = Self::opponent(context);
 
         // Avoid donating 3 coins into a likely Contessa, especially after repeated blocks
-        let streak = Self::assassination_blocked_streak();
-        let p_contessa = Self::p_opponent_has(context, opp.cards as i32, Card::Contessa);
+        let streak = self.assassination_blocked_streak();
+        let p_contessa = self.p_opponent_has(context, opp.cards as i32, Card::Contessa);
 
         if streak >= 1 && p_contessa > 0.55 {
             return false;
@@ -281,11 +279,11 @@ impl DuelBot {
         true
     }
 
-    fn should_challenge_contessa_block(context: &Context) -> bool {
+    fn should_challenge_contessa_block(&self, context: &Context) -> bool {
         let opp = Self::opponent(context);
 
         // Don't spew challenges repeatedly after multiple blocks
-        if Self::assassination_blocked_streak() >= 2 {
+        if self.assassination_blocked_streak() >= 2 {
             return false;
         }
 
@@ -293,7 +291,7 @@ impl DuelBot {
             return true;
         }
 
-        let p_has = Self::p_opponent_has(context, opp.cards as i32, Card::Contessa);
+        let p_has = self.p_opponent_has(context, opp.cards as i32, Card::Contessa);
 
         let v_win = 1.20;
         let mut v_lose = 1.00;
@@ -306,25 +304,25 @@ impl DuelBot {
 
     // -------- Challenging action logic (with "must not lose next turn" boost) --------
 
-    fn required_role_for_action(action: &Action) -> Option<Card> {
+    fn required_role_for_action(context: &Context, action: &Action) -> Option<Card> {
         match action {
             Action::Assassination(_) => Some(Card::Assassin),
-            Action::Swapping => Some(Card::Ambassador),
+            Action::Swapping => Some(context.config.reformation.exchange_card()),
             Action::Stealing(_) => Some(Card::Captain),
             Action::Tax => Some(Card::Duke),
             _ => None,
         }
     }
 
-    fn should_challenge_action(context: &Context, _by: &str, action: &Action) -> bool {
-        let Some(role) = Self::required_role_for_action(action) else { return false };
+    fn should_challenge_action(&self, context: &Context, _by: &str, action: &Action) -> bool {
+        let Some(role) = Self::required_role_for_action(context, action) else { return false };
 
         if Self::remaining_copies(context, role) == 0 {
             return true;
         }
 
         let opp = Self::opponent(context);
-        let p_has = Self::p_opponent_has(context, opp.cards as i32, role);
+        let p_has = self.p_opponent_has(context, opp.cards as i32, role);
 
         // Base weights
         let (mut v_win, mut v_lose) = match action {
@@ -353,87 +351,92 @@ impl BotInterface for DuelBot {
         "DuelBot".to_string()
     }
 
-    fn on_turn(&self, context: &Context) -> Action {
-        Self::update_from_history(context);
+    // Reset memory at the start of a new game
+    fn on_game_start(&mut self, _context: &Context) {
+        self.memory = Memory::default();
+    }
+
+    fn on_turn(&mut self, context: &Context) -> Action {
+        self.update_from_history(context);
 
         let opp = Self::opponent(context);
         let target = opp.name.clone();
 
         // If we can coup, do it.
-        if context.coins >= 7 {
-            Self::set_assassination_pending(false);
+        if context.coins >= context.config.coup_cost {
+            self.set_assassination_pending(false);
             return Action::Coup(target);
         }
 
         // If we will lose next turn to a coup, prioritize "stop coup" or "win now".
         if Self::imminent_coup_loss(context) {
             // Win now if possible: assassination can win immediately if opponent has 1 influence.
-            if opp.cards <= 1 && context.coins >= 3
-                && (context.cards.contains(&Card::Assassin) || Self::bluff_ev_ok(context, Card::Assassin, 1.45, 2.5))
-                && Self::should_attempt_assassination(context)
+            if opp.cards <= 1 && context.coins >= context.config.assassination_cost
+                && (context.cards.contains(&Card::Assassin) || self.bluff_ev_ok(context, Card::Assassin, 1.45, 2.5))
+                && self.should_attempt_assassination(context)
             {
-                Self::set_assassination_pending(true);
+                self.set_assassination_pending(true);
                 return Action::Assassination(target);
             }
 
             // Otherwise prevent coup: steal (even as a bluff) if they have coins.
-            if opp.coins >= 2 && (context.cards.contains(&Card::Captain) || Self::bluff_ev_ok(context, Card::Captain, 1.20, 2.0)) {
-                Self::set_assassination_pending(false);
+            if opp.coins >= 2 && (context.cards.contains(&Card::Captain) || self.bluff_ev_ok(context, Card::Captain, 1.20, 2.0)) {
+                self.set_assassination_pending(false);
                 return Action::Stealing(opp.name.clone());
             }
 
             // If we can't stop coup, take highest-variance "win chance": assassination bluff attempt if legal.
-            if context.coins >= 3 && Self::bluff_ev_ok(context, Card::Assassin, 1.55, 2.2) && Self::should_attempt_assassination(context) {
-                Self::set_assassination_pending(true);
+            if context.coins >= context.config.assassination_cost && self.bluff_ev_ok(context, Card::Assassin, 1.55, 2.2) && self.should_attempt_assassination(context) {
+                self.set_assassination_pending(true);
                 return Action::Assassination(target);
             }
         }
 
         // Assassination pressure (but avoid looping into blocks)
-        if context.coins >= 3
-            && (context.cards.contains(&Card::Assassin) || Self::bluff_ev_ok(context, Card::Assassin, 1.35, 1.8))
-            && Self::should_attempt_assassination(context)
+        if context.coins >= context.config.assassination_cost
+            && (context.cards.contains(&Card::Assassin) || self.bluff_ev_ok(context, Card::Assassin, 1.35, 1.8))
+            && self.should_attempt_assassination(context)
         {
-            Self::set_assassination_pending(true);
+            self.set_assassination_pending(true);
             return Action::Assassination(target);
         }
 
         // Tax (real or EV-positive bluff)
-        if context.cards.contains(&Card::Duke) || Self::bluff_ev_ok(context, Card::Duke, 1.10, 2.0) {
-            Self::set_assassination_pending(false);
+        if context.cards.contains(&Card::Duke) || self.bluff_ev_ok(context, Card::Duke, 1.10, 2.0) {
+            self.set_assassination_pending(false);
             return Action::Tax;
         }
 
         // Steal (real or EV-positive bluff), especially when opponent is close to coup range
-        if opp.coins >= 2 && (context.cards.contains(&Card::Captain) || Self::bluff_ev_ok(context, Card::Captain, 1.05, 1.5)
-            || (Self::opponent_coup_threat_next_turn(context) && Self::bluff_ev_ok(context, Card::Captain, 1.25, 2.0)))
+        if opp.coins >= 2 && (context.cards.contains(&Card::Captain) || self.bluff_ev_ok(context, Card::Captain, 1.05, 1.5)
+            || (Self::opponent_coup_threat_next_turn(context) && self.bluff_ev_ok(context, Card::Captain, 1.25, 2.0)))
         {
-            Self::set_assassination_pending(false);
+            self.set_assassination_pending(false);
             return Action::Stealing(opp.name.clone());
         }
 
         // Opportunistic foreign aid
-        if Self::remaining_copies(context, Card::Duke) >= 2 && context.history.len() % 3 == 0 {
-            Self::set_assassination_pending(false);
+        if Self::remaining_copies(context, Card::Duke) >= 2 && context.history.len().is_multiple_of(3) {
+            self.set_assassination_pending(false);
             return Action::ForeignAid;
         }
 
-        Self::set_assassination_pending(false);
+        self.set_assassination_pending(false);
         Action::Income
     }
 
-    fn on_auto_coup(&self, context: &Context) -> String {
-        Self::update_from_history(context);
+    fn on_auto_coup(&mut self, context: &Context) -> String {
+        self.update_from_history(context);
         Self::opponent(context).name.clone()
     }
 
-    fn on_challenge_action_round(&self, action: &Action, by: String, context: &Context) -> bool {
-        Self::update_from_history(context);
-        by != context.name && Self::should_challenge_action(context, &by, action)
+    fn on_challenge_action_round(&mut self, action: &Action, by: String, context: &Context) -> bool {
+        self.update_from_history(context);
+        by != context.name && self.should_challenge_action(context, &by, action)
     }
 
-    fn on_counter(&self, action: &Action, _by: String, context: &Context) -> bool {
-        Self::update_from_history(context);
+    fn on_counter(&mut self, action: &Action, _by: String, context: &Context) -> bool {
+        self.update_from_history(context);
 
         match action {
             Action::Assassination(_) => {
@@ -441,7 +444,7 @@ impl BotInterface for DuelBot {
                     true
                 } else {
                     // Only bluff-block if the opponent isn't very likely to challenge it
-                    let p_chal = Self::p_opponent_challenges_claim(context, Card::Contessa, 1.35);
+                    let p_chal = self.p_opponent_challenges_claim(context, Card::Contessa, 1.35);
                     Self::remaining_copies(context, Card::Contessa) > 0 && p_chal < 0.35
                 }
             }
@@ -449,17 +452,18 @@ impl BotInterface for DuelBot {
             Action::ForeignAid => {
                 context.cards.contains(&Card::Duke)
                     || (Self::remaining_copies(context, Card::Duke) > 0
-                        && Self::p_opponent_challenges_claim(context, Card::Duke, 1.05) < 0.40)
+                        && self.p_opponent_challenges_claim(context, Card::Duke, 1.05) < 0.40)
             }
 
             Action::Stealing(_) => {
-                if context.cards.contains(&Card::Captain) || context.cards.contains(&Card::Ambassador) {
+                let exchange = context.config.reformation.exchange_card();
+                if context.cards.contains(&Card::Captain) || context.cards.contains(&exchange) {
                     true
                 } else {
                     let cap_ok = Self::remaining_copies(context, Card::Captain) > 0
-                        && Self::p_opponent_challenges_claim(context, Card::Captain, 1.05) < 0.35;
-                    let amb_ok = Self::remaining_copies(context, Card::Ambassador) > 0
-                        && Self::p_opponent_challenges_claim(context, Card::Ambassador, 1.00) < 0.35;
+                        && self.p_opponent_challenges_claim(context, Card::Captain, 1.05) < 0.35;
+                    let amb_ok = Self::remaining_copies(context, exchange) > 0
+                        && self.p_opponent_challenges_claim(context, exchange, 1.00) < 0.35;
                     cap_ok || amb_ok
                 }
             }
@@ -468,27 +472,28 @@ impl BotInterface for DuelBot {
         }
     }
 
-    fn on_challenge_counter_round(&self, action: &Action, _by: String, context: &Context) -> bool {
-        Self::update_from_history(context);
+    fn on_challenge_counter_round(&mut self, action: &Action, _by: String, context: &Context) -> bool {
+        self.update_from_history(context);
 
         match action {
             // If they block our assassination, decide if we should challenge Contessa (EV-based, no repeat spew)
-            Action::Assassination(_) => Self::should_challenge_contessa_block(context),
+            Action::Assassination(_) => self.should_challenge_contessa_block(context),
 
             // Hard-proof / safe checks
             Action::ForeignAid => Self::remaining_copies(context, Card::Duke) == 0,
 
             Action::Stealing(_) => {
-                // They can block steal with Captain OR Ambassador.
+                // They can block steal with Captain OR the exchange card (Ambassador or Inquisitor).
                 // In desperation (we die next turn), challenge more often if it might save us.
+                let exchange = context.config.reformation.exchange_card();
                 if Self::imminent_coup_loss(context) {
                     // If either role is "rare", they're more likely bluffing.
                     let cap_rem = Self::remaining_copies(context, Card::Captain);
-                    let amb_rem = Self::remaining_copies(context, Card::Ambassador);
+                    let amb_rem = Self::remaining_copies(context, exchange);
                     (cap_rem == 0 && amb_rem == 0) || (cap_rem <= 1 && amb_rem <= 1)
                 } else {
                     Self::remaining_copies(context, Card::Captain) == 0
-                        && Self::remaining_copies(context, Card::Ambassador) == 0
+                        && Self::remaining_copies(context, exchange) == 0
                 }
             }
 
@@ -496,8 +501,8 @@ impl BotInterface for DuelBot {
         }
     }
 
-    fn on_swapping_cards(&self, new_cards: [Card; 2], context: &Context) -> [Card; 2] {
-        Self::update_from_history(context);
+    fn on_swapping_cards(&mut self, new_cards: [Card; 2], context: &Context) -> [Card; 2] {
+        self.update_from_history(context);
 
         fn rank(c: Card) -> u8 {
             match c {
@@ -505,11 +510,11 @@ impl BotInterface for DuelBot {
                 Card::Assassin => 4,
                 Card::Contessa => 3,
                 Card::Captain => 2,
-                Card::Ambassador => 1,
+                Card::Ambassador | Card::Inquisitor => 1,
             }
         }
 
-        let mut pool = vec![context.cards[0], context.cards[1], new_cards[0], new_cards[1]];
+        let mut pool = [context.cards[0], context.cards[1], new_cards[0], new_cards[1]];
         pool.sort_by_key(|c| std::cmp::Reverse(rank(*c)));
 
         let keep1 = pool[0];
@@ -529,8 +534,29 @@ impl BotInterface for DuelBot {
         [discarded[0], discarded[1]]
     }
 
-    fn on_card_loss(&self, context: &Context) -> Card {
-        Self::update_from_history(context);
+    fn on_starting_cards(&mut self, dealt_cards: Vec<Card>, _context: &Context) -> [Card; 2] {
+        // same order as when swapping but two different roles give us more to claim honestly
+        fn rank(c: Card) -> u8 {
+            match c {
+                Card::Duke => 5,
+                Card::Assassin => 4,
+                Card::Contessa => 3,
+                Card::Captain => 2,
+                Card::Ambassador | Card::Inquisitor => 1,
+            }
+        }
+
+        let mut pool = dealt_cards;
+        pool.sort_by_key(|c| std::cmp::Reverse(rank(*c)));
+
+        let keep1 = pool[0];
+        let keep2 = pool.iter().copied().find(|c| *c != keep1).unwrap_or(pool[1]);
+
+        [keep1, keep2]
+    }
+
+    fn on_card_loss(&mut self, context: &Context) -> Card {
+        self.update_from_history(context);
 
         fn rank(c: Card) -> u8 {
             match c {
@@ -538,7 +564,7 @@ impl BotInterface for DuelBot {
                 Card::Contessa => 4,
                 Card::Assassin => 3,
                 Card::Captain => 2,
-                Card::Ambassador => 1,
+                Card::Ambassador | Card::Inquisitor => 1,
             }
         }
 
@@ -546,3 +572,34 @@ impl BotInterface for DuelBot {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::OtherBot;
+    use crate::{GameConfig, Reformation};
+
+    #[test]
+    fn test_challenge_inquisitor_swap() {
+        let config = GameConfig { reformation: Reformation { factions: false, inquisitor: true }, ..Default::default() };
+        let bot_info = |name: &str| OtherBot { name: name.to_string(), coins: 2, cards: 2, allegiance: None };
+        // we can see all three inquisitors so the swap has to be a bluff
+        let context = Context {
+            name: "me".to_string(),
+            cards: vec![Card::Inquisitor, Card::Inquisitor],
+            coins: 2,
+            playing_bots: vec![bot_info("me"), bot_info("a")],
+            discard_pile: vec![Card::Inquisitor],
+            history: vec![History::ActionSwapping { by: "a".to_string() }],
+            moves: 1,
+            score: vec![],
+            config,
+            treasury: 46,
+            treasury_reserve: 0,
+            copies: 3,
+        };
+
+        let mut bot = DuelBot::default();
+        assert!(bot.on_challenge_action_round(&Action::Swapping, "a".to_string(), &context));
+    }
+}
@@ -18,15 +18,13 @@ impl BotInterface for HonestBot {
 
 	/// Acts on cards it has and falls back to [Action::Income].
 	/// Never plays [Action::ForeignAid] or [Action::Swapping].
-	fn on_turn(&self, context: &Context) -> Action {
-		let target = context
-			.playing_bots
-			.iter()
-			.filter(|bot| bot.name != context.name)
-			.min_by_key(|bot| bot.cards)
-			.unwrap();
+	fn on_turn(&mut self, context: &Context) -> Action {
+		let target =
+			context.targets().into_iter().min_by_key(|bot| bot.cards).unwrap();
 
-		if context.cards.contains(&Card::Assassin) && context.coins >= 3 {
+		if context.cards.contains(&Card::Assassin)
+			&& context.coins >= context.config.assassination_cost
+		{
 			Action::Assassination(target.name.clone())
 		} else if context.cards.contains(&Card::Captain) {
 			Action::Stealing(target.name.clone())
@@ -38,20 +36,16 @@ impl BotInterface for HonestBot {
 	}
 
 	/// Looks for the bot with the least cards
-	fn on_auto_coup(&self, context: &Context) -> String {
-		let target = context
-			.playing_bots
-			.iter()
-			.filter(|bot| bot.name != context.name)
-			.min_by_key(|bot| bot.cards)
-			.unwrap();
+	fn on_auto_coup(&mut self, context: &Context) -> String {
+		let target =
+			context.targets().into_iter().min_by_key(|bot| bot.cards).unwrap();
 		target.name.clone()
 	}
 
-	/// Challenges only if it can see all three cards associated with the current
-	/// action in either the discard pile or its own hand.
+	/// Challenges only if it can see every copy of the card associated with the
+	/// current action in either the discard pile or its own hand.
 	fn on_challenge_action_round(
-		&self,
+		&mut self,
 		action: &Action,
 		_by: String,
 		context: &Context,
@@ -62,23 +56,34 @@ impl BotInterface for HonestBot {
 		match action {
 			Action::Assassination(_) => {
 				all_visible_cards.iter().filter(|card| **card == Card::Assassin).count()
-					== 3
+					== context.copies
 			},
 			Action::Swapping => {
 				all_visible_cards
 					.iter()
-					.filter(|card| **card == Card::Ambassador)
-					.count() == 3
+					.filter(|card| **card == context.config.reformation.exchange_card())
+					.count() == context.copies
 			},
 			Action::Stealing(_) => {
 				all_visible_cards.iter().filter(|card| **card == Card::Captain).count()
-					== 3
+					== context.copies
 			},
 			Action::Tax => {
 				all_visible_cards.iter().filter(|card| **card == Card::Duke).count()
-					== 3
+					== context.copies
 			},
-			Action::Coup(_) | Action::ForeignAid | Action::Income => {
+			Action::Examine(_) => {
+				all_visible_cards
+					.iter()
+					.filter(|card| **card == Card::Inquisitor)
+					.count() == context.copies
+			},
+			// It can never see that a bot has a Duke
+			Action::Embezzlement => false,
+			Action::Coup(_)
+			| Action::ForeignAid
+			| Action::Income
+			| Action::Conversion(_) => {
 				unreachable!("Can't challenge couping or Income")
 			},
 		}
@@ -86,7 +91,7 @@ impl BotInterface for HonestBot {
 
 	/// Counters only if it has the card to counter
 	fn on_counter(
-		&self,
+		&mut self,
 		action: &Action,
 		_by: String,
 		context: &Context,
@@ -96,18 +101,24 @@ impl BotInterface for HonestBot {
 			Action::ForeignAid => context.cards.contains(&Card::Duke),
 			Action::Stealing(_) => {
 				context.cards.contains(&Card::Captain)
-					|| context.cards.contains(&Card::Ambassador)
+					|| context.cards.contains(&context.config.reformation.exchange_card())
 			},
-			Action::Coup(_) | Action::Swapping | Action::Income | Action::Tax => {
+			Action::Coup(_)
+			| Action::Swapping
+			| Action::Income
+			| Action::Tax
+			| Action::Conversion(_)
+			| Action::Embezzlement
+			| Action::Examine(_) => {
 				unreachable!("Can't challenge couping or Income")
 			},
 		}
 	}
 
-	/// Counter-challenges only if it can see all three cards associated with the
-	/// current action in either the discard pile or its own hand.
+	/// Counter-challenges only if it can see every copy of the cards associated
+	/// with the current action in either the discard pile or its own hand.
 	fn on_challenge_counter_round(
-		&self,
+		&mut self,
 		action: &Action,
 		_by: String,
 		context: &Context,
@@ -118,17 +129,23 @@ impl BotInterface for HonestBot {
 		match action {
 			Action::Assassination(_) => {
 				all_visible_cards.iter().filter(|card| **card == Card::Contessa).count()
-					== 3
+					== context.copies
 			},
 			Action::ForeignAid => context.cards.contains(&Card::Duke),
 			Action::Stealing(_) => {
 				all_visible_cards.iter().filter(|card| **card == Card::Captain).count()
-					== 3 && all_visible_cards
+					== context.copies && all_visible_cards
 					.iter()
-					.filter(|card| **card == Card::Ambassador)
-					.count() == 3
+					.filter(|card| **card == context.config.reformation.exchange_card())
+					.count() == context.copies
 			},
-			Action::Coup(_) | Action::Income | Action::Swapping | Action::Tax => {
+			Action::Coup(_)
+			| Action::Income
+			| Action::Swapping
+			| Action::Tax
+			| Action::Conversion(_)
+			| Action::Embezzlement
+			| Action::Examine(_) => {
 				unreachable!("Can't challenge couping or Income")
 			},
 		}
@@ -136,7 +153,7 @@ impl BotInterface for HonestBot {
 
 	/// Swaps duplicate cards
 	fn on_swapping_cards(
-		&self,
+		&mut self,
 		new_cards: [Card; 2],
 		context: &Context,
 	) -> [Card; 2] {
@@ -151,8 +168,17 @@ impl BotInterface for HonestBot {
 		[discard_cards[0], discard_cards[1]]
 	}
 
+	/// Swaps a duplicate card
+	fn on_exchanging_card(&mut self, new_card: Card, context: &Context) -> Card {
+		if context.cards.len() == 2 && context.cards[0] == context.cards[1] {
+			context.cards[0]
+		} else {
+			new_card
+		}
+	}
+
 	/// Takes the first card to discard
-	fn on_card_loss(&self, context: &Context) -> Card {
+	fn on_card_loss(&mut self, context: &Context) -> Card {
 		context.cards.clone().pop().unwrap()
 	}
 }
@@ -0,0 +1,448 @@
+//! A bot that lets a person play at the table via the terminal.
+
+use std::io::{self, BufRead, BufReader, Write};
+
+use crate::{
+	bot::{BotInterface, Context},
+	Action, Card,
+};
+
+/// The human bot asks a person for each decision.
+/// It shows your cards, coins, the other bots, the discard pile and the recent
+/// history before each question and keeps asking until the answer is valid so
+/// a typo never gets you penalized.
+/// When the input runs out it falls back to the moves of the
+/// [crate::bots::StaticBot].
+///
+/// ```rust,no_run
+/// use coup::{
+///     bots::{HonestBot, HumanBot},
+///     Coup,
+/// };
+///
+/// let mut coup_game =
+///     Coup::new(vec![Box::new(HumanBot::new("You")), Box::new(HonestBot)]);
+/// coup_game.play();
+/// ```
+pub struct HumanBot {
+	name: String,
+	input: Box<dyn BufRead>,
+	output: Box<dyn Write>,
+}
+
+impl HumanBot {
+	/// Create a new human player reading from stdin and writing to stdout.
+	pub fn new(name: &str) -> Self {
+		Self::with_io(name, BufReader::new(io::stdin()), io::stdout())
+	}
+
+	/// Create a new human player reading answers from the input and writing
+	/// questions to the output you pass in.
+	pub fn with_io(
+		name: &str,
+		input: impl BufRead + 'static,
+		output: impl Write + 'static,
+	) -> Self {
+		Self {
+			name: String::from(name),
+			input: Box::new(input),
+			output: Box::new(output),
+		}
+	}
+
+	fn write(&mut self, text: &str) {
+		// There is nothing we can do if the terminal is gone
+		let _ =
+			self.output.write_all(text.as_bytes()).and_then(|_| self.output.flush());
+	}
+
+	fn show_context(&mut self, context: &Context) {
+		let mut text = String::from("\n ┌─ Waiting for your decision\n");
+		text.push_str(&format!(
+			" │ Cards: \x1b[33m{:?}\x1b[39m  Coins: \x1b[33m{}\x1b[39m\n",
+			context.cards, context.coins
+		));
+		text.push_str(" │ Bots:\n");
+		for bot in context.playing_bots.iter() {
+			text.push_str(&format!(
+				" │   {}{} \x1b[90m{} cards, {} coins{}\x1b[39m\n",
+				bot.name,
+				if bot.name == context.name {
+					" (you)"
+				} else {
+					""
+				},
+				bot.cards,
+				bot.coins,
+				match bot.allegiance {
+					Some(allegiance) => format!(", {:?}", allegiance),
+					None => String::new(),
+				}
+			));
+		}
+		text.push_str(&format!(" │ Discard pile: {:?}\n", context.discard_pile));
+		text.push_str(&format!(" │ Treasury: {} coins\n", context.treasury));
+		if context.config.reformation.factions {
+			text.push_str(&format!(
+				" │ Treasury Reserve: {} coins\n",
+				context.treasury_reserve
+			));
+		}
+		text.push_str(" │ Recent history:\n");
+		for event in
+			context.history.iter().skip(context.history.len().saturating_sub(5))
+		{
+			text.push_str(&format!(" │   \x1b[90m{:?}\x1b[39m\n", event));
+		}
+		text.push_str(" └─\n");
+		self.write(&text);
+	}
+
+	// Keep asking until the answer parses or return the fallback when the input
+	// has run out
+	fn ask<T>(
+		&mut self,
+		question: &str,
+		parse: impl Fn(&str) -> Option<T>,
+		fallback: T,
+	) -> T {
+		loop {
+			self.write(&format!(" {} ", question));
+
+			let mut answer = String::new();
+			match self.input.read_line(&mut answer) {
+				Ok(0) | Err(_) => {
+					self.write("\n");
+					return fallback;
+				},
+				Ok(_) => {},
+			}
+
+			if let Some(value) = parse(answer.trim()) {
+				return value;
+			}
+			self.write(" \x1b[31mThat's not a valid answer, try again\x1b[39m\n");
+		}
+	}
+
+	fn ask_yes_no(&mut self, question: &str) -> bool {
+		self.ask(
+			&format!("{} [y/n]", question),
+			|answer| match answer.to_lowercase().as_str() {
+				"y" | "yes" => Some(true),
+				"n" | "no" => Some(false),
+				_ => None,
+			},
+			false,
+		)
+	}
+
+	fn ask_target(&mut self, context: &Context) -> String {
+		let targets = context
+			.targets()
+			.iter()
+			.map(|bot| bot.name.clone())
+			.collect::<Vec<String>>();
+		self.ask_bot(targets)
+	}
+
+	fn ask_bot(&mut self, targets: Vec<String>) -> String {
+		let question = targets
+			.iter()
+			.enumerate()
+			.map(|(index, name)| format!("{}) {}", index + 1, name))
+			.collect::<Vec<String>>()
+			.join("  ");
+
+		self.ask(
+			&format!("Pick a target: {}", question),
+			|answer| pick(&targets, answer).cloned(),
+			targets[0].clone(),
+		)
+	}
+}
+
+// Pick an item either by its number starting at 1 or by its name
+fn pick<'a, T: std::fmt::Debug>(items: &'a [T], answer: &str) -> Option<&'a T> {
+	match answer.parse::<usize>() {
+		Ok(number) => items.get(number.checked_sub(1)?),
+		Err(_) => items.iter().find(|item| {
+			format!("{:?}", item).trim_matches('"').eq_ignore_ascii_case(answer)
+		}),
+	}
+}
+
+impl BotInterface for HumanBot {
+	/// The name you gave this player
+	fn get_name(&self) -> String {
+		self.name.clone()
+	}
+
+	/// Asks which action to take and for a target if the action needs one.
+	/// Only actions you can afford are offered.
+	fn on_turn(&mut self, context: &Context) -> Action {
+		self.show_context(context);
+
+		let mut actions =
+			vec!["Income", "ForeignAid", "Tax", "Stealing", "Swapping"];
+		if context.coins >= context.config.assassination_cost {
+			actions.push("Assassination");
+		}
+		if context.coins >= context.config.coup_cost {
+			actions.push("Coup");
+		}
+		if context.config.reformation.factions {
+			actions.push("Embezzlement");
+			if context.coins >= 1 {
+				actions.push("Conversion");
+			}
+		}
+		if context.config.reformation.inquisitor {
+			actions.push("Examine");
+		}
+
+		let question = actions
+			.iter()
+			.enumerate()
+			.map(|(index, action)| format!("{}) {}", index + 1, action))
+			.collect::<Vec<String>>()
+			.join("  ");
+		let action = self.ask(
+			&format!("Pick an action: {}", question),
+			|answer| pick(&actions, answer).copied(),
+			"Income",
+		);
+
+		match action {
+			"ForeignAid" => Action::ForeignAid,
+			"Tax" => Action::Tax,
+			"Stealing" => Action::Stealing(self.ask_target(context)),
+			"Swapping" => Action::Swapping,
+			"Assassination" => Action::Assassination(self.ask_target(context)),
+			"Coup" => Action::Coup(self.ask_target(context)),
+			"Embezzlement" => Action::Embezzlement,
+			"Conversion" => {
+				self.write(" Converting yourself costs 1 coin, anyone else 2\n");
+				// With a single coin you can only afford to convert yourself
+				Action::Conversion(
+					self.ask_bot(
+						context
+							.playing_bots
+							.iter()
+							.filter(|bot| bot.name == context.name || context.coins >= 2)
+							.map(|bot| bot.name.clone())
+							.collect(),
+					),
+				)
+			},
+			"Examine" => Action::Examine(self.ask_target(context)),
+			_ => Action::Income,
+		}
+	}
+
+	/// Asks who to coup
+	fn on_auto_coup(&mut self, context: &Context) -> String {
+		self.show_context(context);
+		self.write(&format!(
+			" You have {} coins or more and must coup\n",
+			context.config.auto_coup_coins
+		));
+		self.ask_target(context)
+	}
+
+	/// Asks whether to challenge the action
+	fn on_challenge_action_round(
+		&mut self,
+		action: &Action,
+		by: String,
+		context: &Context,
+	) -> bool {
+		self.show_context(context);
+		self.ask_yes_no(&format!("{} played {:?}. Do you challenge?", by, action))
+	}
+
+	/// Asks whether to counter the action
+	fn on_counter(
+		&mut self,
+		action: &Action,
+		by: String,
+		context: &Context,
+	) -> bool {
+		self.show_context(context);
+		self.ask_yes_no(&format!("{} played {:?}. Do you counter?", by, action))
+	}
+
+	/// Asks whether to challenge the counter
+	fn on_challenge_counter_round(
+		&mut self,
+		action: &Action,
+		by: String,
+		context: &Context,
+	) -> bool {
+		self.show_context(context);
+		self.ask_yes_no(&format!(
+			"{} countered {:?}. Do you challenge the counter?",
+			by, action
+		))
+	}
+
+	/// Asks which two cards to give back to the deck
+	fn on_swapping_cards(
+		&mut self,
+		new_cards: [Card; 2],
+		context: &Context,
+	) -> [Card; 2] {
+		self.show_context(context);
+
+		let mut all_cards = context.cards.clone();
+		all_cards.extend(new_cards);
+		let question = all_cards
+			.iter()
+			.enumerate()
+			.map(|(index, card)| format!("{}) {:?}", index + 1, card))
+			.collect::<Vec<String>>()
+			.join("  ");
+
+		self.ask(
+			&format!("Pick two cards to give back (e.g. \"1 2\"): {}", question),
+			|answer| {
+				let picked = answer
+					.split(|c: char| c == ',' || c.is_whitespace())
+					.filter(|part| !part.is_empty())
+					.map(|part| part.parse::<usize>().ok()?.checked_sub(1))
+					.collect::<Option<Vec<usize>>>()?;
+
+				match picked[..] {
+					[first, second]
+						if first != second
+							&& first < all_cards.len()
+							&& second < all_cards.len() =>
+					{
+						Some([all_cards[first], all_cards[second]])
+					},
+					_ => None,
+				}
+			},
+			new_cards,
+		)
+	}
+
+	/// Asks which card to give back to the deck
+	fn on_exchanging_card(&mut self, new_card: Card, context: &Context) -> Card {
+		self.show_context(context);
+
+		let mut all_cards = context.cards.clone();
+		all_cards.push(new_card);
+		let question = all_cards
+			.iter()
+			.enumerate()
+			.map(|(index, card)| format!("{}) {:?}", index + 1, card))
+			.collect::<Vec<String>>()
+			.join("  ");
+		self.ask(
+			&format!(
+				"You drew {:?}, pick a card to give back: {}",
+				new_card, question
+			),
+			|answer| pick(&all_cards, answer).copied(),
+			new_card,
+		)
+	}
+
+	/// Asks which card to show unless there is only one choice
+	fn on_examine(&mut self, by: String, context: &Context) -> Card {
+		let mut cards = context.cards.clone();
+		cards.dedup();
+		if cards.len() == 1 {
+			return cards[0];
+		}
+
+		self.show_context(context);
+		let question = cards
+			.iter()
+			.enumerate()
+			.map(|(index, card)| format!("{}) {:?}", index + 1, card))
+			.collect::<Vec<String>>()
+			.join("  ");
+		self.ask(
+			&format!("{} examines you, pick a card to show: {}", by, question),
+			|answer| pick(&cards, answer).copied(),
+			cards[0],
+		)
+	}
+
+	/// Asks whether the other bot has to exchange the card it showed
+	fn on_examined_card(
+		&mut self,
+		card: Card,
+		target: String,
+		context: &Context,
+	) -> bool {
+		self.show_context(context);
+		self.ask_yes_no(&format!(
+			"{} showed you {:?}. Do you make it exchange the card?",
+			target, card
+		))
+	}
+
+	/// Asks which two of the dealt cards to keep
+	fn on_starting_cards(
+		&mut self,
+		dealt_cards: Vec<Card>,
+		context: &Context,
+	) -> [Card; 2] {
+		self.show_context(context);
+
+		let question = dealt_cards
+			.iter()
+			.enumerate()
+			.map(|(index, card)| format!("{}) {:?}", index + 1, card))
+			.collect::<Vec<String>>()
+			.join("  ");
+
+		self.ask(
+			&format!("Pick two cards to keep (e.g. \"1 2\"): {}", question),
+			|answer| {
+				let picked = answer
+					.split(|c: char| c == ',' || c.is_whitespace())
+					.filter(|part| !part.is_empty())
+					.map(|part| part.parse::<usize>().ok()?.checked_sub(1))
+					.collect::<Option<Vec<usize>>>()?;
+
+				match picked[..] {
+					[first, second]
+						if first != second
+							&& first < dealt_cards.len()
+							&& second < dealt_cards.len() =>
+					{
+						Some([dealt_cards[first], dealt_cards[second]])
+					},
+					_ => None,
+				}
+			},
+			[dealt_cards[0], dealt_cards[1]],
+		)
+	}
+
+	/// Asks which card to lose unless there is only one choice
+	fn on_card_loss(&mut self, context: &Context) -> Card {
+		let mut cards = context.cards.clone();
+		cards.dedup();
+		if cards.len() == 1 {
+			return cards[0];
+		}
+
+		self.show_context(context);
+		let question = cards
+			.iter()
+			.enumerate()
+			.map(|(index, card)| format!("{}) {:?}", index + 1, card))
+			.collect::<Vec<String>>()
+			.join("  ");
+		self.ask(
+			&format!("You lost an influence, pick a card to give up: {}", question),
+			|answer| pick(&cards, answer).copied(),
+			cards[0],
+		)
+	}
+}
@@ -1,38 +1,379 @@
 // basically we’re trying to make the bot think ahead using simulations
-// rn this is a simple rollout-based version (not full UCT yet)
-// but it runs and integrates with the engine
+// each decision runs a UCT search (see mcts/tree.rs) over a full model of the game
+// not just our turn: challenges, counters, swaps and which card to lose are
+// searched too, so bluffing against us isn't free anymore
+//
+// how hard it thinks is up to the MctsConfig, e.g. a quick bot for big
+// loops and a slow one for games people watch:
+//   MctsBot::with_config(MctsConfig { iterations: 50, ..Default::default() })
+//   MctsBot::with_config(MctsConfig {
+//       iterations: usize::MAX,
+//       time_budget: Some(Duration::from_secs(1)),
+//       ..Default::default()
+//   })
+//
+// we keep the tree of our last decision and carry on with it next time, the
+// history since then tells us which way the game went (see Mcts::reroot)
 
 use crate::bot::{BotInterface, Context};
-use crate::Action;
-use crate::mcts::sim_state::SimState;
-use crate::mcts::tree::Mcts;
+use crate::{Action, Card, History};
+use crate::mcts::determinize::Determinizer;
+use crate::mcts::sim_state::{card_rank, Move, Phase, SimState};
+use crate::mcts::tree::{Mcts, MctsConfig};
 
-pub struct MctsBot;
+#[derive(Clone, Debug, Default)]
+pub struct MctsBot {
+    config: MctsConfig,
+    // the tree of our last decision, how much history we had seen by then
+    // and what we did (passes and swaps don't show up in the history)
+    tree: Option<Mcts>,
+    seen: usize,
+    last_move: Option<Move>,
+}
+
+impl MctsBot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(config: MctsConfig) -> Self {
+        Self { config, ..Default::default() }
+    }
+
+    pub fn config(&self) -> &MctsConfig {
+        &self.config
+    }
+
+    // the search behind our last decision, use it to see why we did what we did:
+    //   let search = bot.last_search().unwrap();
+    //   search.root_stats();           // visits, mean reward and confidence per move
+    //   search.principal_variation();  // how we think the game goes on
+    //   search.to_dot(2);              // the top of the tree for graphviz
+    pub fn last_search(&self) -> Option<&Mcts> {
+        self.tree.as_ref()
+    }
+
+    // run the search from the given decision and return the move it likes best
+    fn search(&mut self, sim: SimState, context: &Context) -> Option<Move> {
+        let reused = if self.config.reuse_tree { self.reuse(&sim, context) } else { None };
+        let mcts = reused.unwrap_or_else(|| Mcts::with_config(sim, self.config.clone()));
+
+        // every iteration deals the cards we can't see again, guided by
+        // what the other bots claimed so far
+        let determinizer = Determinizer::from_context(context);
+        let mut mcts = mcts.with_determinizer(determinizer);
+        mcts.search();
+
+        let best = mcts.best_move();
+        self.tree = Some(mcts);
+        self.seen = context.history.len();
+        best
+    }
+
+    // remember what we really did so we can find it in the tree next time
+    fn played(&mut self, mv: Move) {
+        self.last_move = Some(mv);
+    }
+
+    // turn the history since our last decision into moves and look for them
+    // in the old tree
+    fn reuse(&self, sim: &SimState, context: &Context) -> Option<Mcts> {
+        let tree = self.tree.as_ref()?;
+        let me = &context.name;
+
+        let mut observed = vec![];
+        if let Some(mv @ (Move::Pass | Move::Return(_) | Move::ReturnOne(_))) = &self.last_move {
+            observed.push((me.clone(), mv.clone()));
+        }
+
+        for event in context.history.get(self.seen..)? {
+            let mv = match event {
+                History::ActionAssassination { by, target } => (by, Move::Act(Action::Assassination(target.clone()))),
+                History::ActionCoup { by, target } => (by, Move::Act(Action::Coup(target.clone()))),
+                History::ActionForeignAid { by } => (by, Move::Act(Action::ForeignAid)),
+                History::ActionSwapping { by } => (by, Move::Act(Action::Swapping)),
+                History::ActionIncome { by } => (by, Move::Act(Action::Income)),
+                History::ActionStealing { by, target } => (by, Move::Act(Action::Stealing(target.clone()))),
+                History::ActionTax { by } => (by, Move::Act(Action::Tax)),
+                History::ActionConversion { by, target } => (by, Move::Act(Action::Conversion(target.clone()))),
+                History::ActionEmbezzlement { by } => (by, Move::Act(Action::Embezzlement)),
+                History::ChallengeAssassin { by, .. }
+                | History::ChallengeAmbassador { by, .. }
+                | History::ChallengeCaptain { by, .. }
+                | History::ChallengeDuke { by, .. }
+                | History::ChallengeInquisitor { by, .. }
+                | History::ChallengeEmbezzlement { by, .. }
+                | History::CounterChallengeContessa { by, .. }
+                | History::CounterChallengeDuke { by, .. }
+                | History::CounterChallengeCaptainAmbassedor { by, .. }
+                | History::CounterChallengeCaptainInquisitor { by, .. } => (by, Move::Challenge),
+                History::CounterAssassination { by, .. }
+                | History::CounterForeignAid { by, .. }
+                | History::CounterStealing { by, .. } => (by, Move::Counter),
+                History::LoseInfluence { by, card } => (by, Move::Lose(*card)),
+                // our own swap is in last_move already
+                History::ExchangeCards { by } if by == me => continue,
+                // we can't know what others put back and penalties aren't in the sim
+                History::ExchangeCards { .. } | History::Penalty { .. } => return None,
+                // neither is examining
+                History::ActionExamine { .. } | History::ExamineCard { .. } | History::ForceExchange { .. } => {
+                    return None
+                }
+                _ => continue,
+            };
+            observed.push((mv.0.clone(), mv.1));
+        }
+
+        tree.reroot(&observed, sim.clone())
+    }
+
+    // the sim with `actor` on turn, we still have to set the phase
+    fn sim_for(context: &Context, actor: &str) -> SimState {
+        let mut sim = SimState::from_context(context, &mut rand::thread_rng());
+        if let Some(actor) = sim.player_index(actor) {
+            sim.turn = actor;
+        }
+        sim
+    }
+
+    // everyone after `from` who still gets asked, starting with us
+    // (the ones before us already passed)
+    fn pending_from_me(sim: &SimState, from: usize) -> Vec<usize> {
+        sim.others_in_order(from)
+            .into_iter()
+            .skip_while(|index| *index != sim.me)
+            .collect()
+    }
+
+    // the engine keeps asking us for the rest of the turn after we lost our
+    // last card but by then we're not in the sim anymore
+    fn is_out(context: &Context) -> bool {
+        context.cards.is_empty()
+    }
+
+    // the engine doesn't tell us whose turn it is so we look for the last action
+    fn last_actor(context: &Context) -> String {
+        context
+            .history
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                History::ActionAssassination { by, .. }
+                | History::ActionCoup { by, .. }
+                | History::ActionForeignAid { by }
+                | History::ActionSwapping { by }
+                | History::ActionIncome { by }
+                | History::ActionStealing { by, .. }
+                | History::ActionTax { by }
+                | History::ActionConversion { by, .. }
+                | History::ActionEmbezzlement { by }
+                | History::ActionExamine { by, .. } => Some(by.clone()),
+                _ => None,
+            })
+            .unwrap_or_else(|| context.name.clone())
+    }
+}
 
 impl BotInterface for MctsBot {
     fn get_name(&self) -> String {
         String::from("MCTSBot")
     }
-    fn on_turn(&self, context: &Context) -> Action {
-        println!("MCTSBot deciding...");
-    
-        // convert engine Context into our simplified simulation state
+
+    // a new game, the old tree is no use anymore
+    fn on_game_start(&mut self, _context: &Context) {
+        self.tree = None;
+        self.seen = 0;
+        self.last_move = None;
+    }
+
+    fn on_turn(&mut self, context: &Context) -> Action {
+        // convert engine Context into our simulation state
         // this is what MCTS actually reasons over
-        let sim = SimState::from_context(context);
+        let sim = Self::sim_for(context, &context.name);
 
         // run a bunch of simulations to evaluate possible actions
         // more iterations = more thinking (but slower)
-        let mut mcts = Mcts::new(sim);
-        mcts.search(200);
+        // then pick the most visited action from the search
+        let action = match self.search(sim, context) {
+            Some(Move::Act(action)) => action,
+            _ => Action::Income,
+        };
 
-        // pick the best action based on simulation results
-
-    
-        let action = mcts.best_action().unwrap_or(Action::Income);
-    
-        println!("MCTSBot chose: {:?}", action);
-    
+        self.played(Move::Act(action.clone()));
         action
     }
-    
+
+    fn on_auto_coup(&mut self, context: &Context) -> String {
+        // with 10 coins coup is the only legal action so the search just picks the target
+        let sim = Self::sim_for(context, &context.name);
+        let target = match self.search(sim, context) {
+            Some(Move::Act(Action::Coup(target))) if context.can_target(&target) => target,
+            // there is always someone to coup, an empty name would only get us penalized
+            _ => context.targets()[0].name.clone(),
+        };
+
+        self.played(Move::Act(Action::Coup(target.clone())));
+        target
+    }
+
+    fn on_challenge_action_round(&mut self, action: &Action, by: String, context: &Context) -> bool {
+        if Self::is_out(context) {
+            return false;
+        }
+
+        let mut sim = Self::sim_for(context, &by);
+        let pending = Self::pending_from_me(&sim, sim.turn);
+        sim.phase = Phase::ChallengeAction { action: action.clone(), pending };
+
+        let challenge = self.search(sim, context) == Some(Move::Challenge);
+        self.played(if challenge { Move::Challenge } else { Move::Pass });
+        challenge
+    }
+
+    fn on_counter(&mut self, action: &Action, by: String, context: &Context) -> bool {
+        if Self::is_out(context) {
+            return false;
+        }
+
+        let mut sim = Self::sim_for(context, &by);
+        // foreign aid can be blocked by anyone, the rest only by us as the target
+        let pending = match action {
+            Action::ForeignAid => Self::pending_from_me(&sim, sim.turn),
+            _ => vec![sim.me],
+        };
+        sim.phase = Phase::Counter { action: action.clone(), pending };
+
+        let counter = self.search(sim, context) == Some(Move::Counter);
+        self.played(if counter { Move::Counter } else { Move::Pass });
+        counter
+    }
+
+    fn on_challenge_counter_round(&mut self, action: &Action, by: String, context: &Context) -> bool {
+        if Self::is_out(context) {
+            return false;
+        }
+
+        let mut sim = Self::sim_for(context, &Self::last_actor(context));
+        let Some(blocker) = sim.player_index(&by) else {
+            return false;
+        };
+        let pending = Self::pending_from_me(&sim, blocker);
+        sim.phase = Phase::ChallengeCounter { action: action.clone(), blocker, pending };
+
+        let challenge = self.search(sim, context) == Some(Move::Challenge);
+        self.played(if challenge { Move::Challenge } else { Move::Pass });
+        challenge
+    }
+
+    fn on_swapping_cards(&mut self, new_cards: [Card; 2], context: &Context) -> [Card; 2] {
+        // the new cards are ours now, so they can't be dealt to anyone else
+        let mut context = context.clone();
+        context.cards.extend(new_cards);
+
+        let mut sim = Self::sim_for(&context, &context.name);
+        sim.phase = Phase::Swap;
+
+        let mut cards = match self.search(sim, &context) {
+            Some(Move::Return(cards)) => cards,
+            _ => new_cards,
+        };
+
+        // the sim keeps the pair sorted
+        cards.sort_by_key(|card| card_rank(*card));
+        self.played(Move::Return(cards));
+        cards
+    }
+
+    fn on_exchanging_card(&mut self, new_card: Card, context: &Context) -> Card {
+        // same as swapping but with the one card the inquisitor drew
+        let mut context = context.clone();
+        context.cards.push(new_card);
+
+        let mut sim = Self::sim_for(&context, &context.name);
+        sim.phase = Phase::Exchange;
+
+        let card = match self.search(sim, &context) {
+            Some(Move::ReturnOne(card)) => card,
+            _ => new_card,
+        };
+
+        self.played(Move::ReturnOne(card));
+        card
+    }
+
+    fn on_card_loss(&mut self, context: &Context) -> Card {
+        // we don't know how the rest of the turn goes so we just end it after
+        let mut sim = Self::sim_for(context, &Self::last_actor(context));
+        sim.phase = Phase::LoseCard { player: sim.me, then: Box::new(Phase::EndTurn) };
+
+        let card = match self.search(sim, context) {
+            Some(Move::Lose(card)) => card,
+            _ => context.cards[0],
+        };
+
+        self.played(Move::Lose(card));
+        card
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::OtherBot;
+    use crate::mcts::sim_state::SimPlayer;
+    use crate::{GameConfig, Reformation};
+
+    #[test]
+    fn test_reuse_inquisitor_challenge() {
+        let config = GameConfig { reformation: Reformation { factions: false, inquisitor: true }, ..Default::default() };
+        let player = |name: &str, cards: Vec<Card>| SimPlayer { name: name.to_string(), cards, coins: 2, allegiance: None };
+        let state = SimState {
+            me: 0,
+            players: vec![
+                player("me", vec![Card::Duke, Card::Captain]),
+                player("a", vec![Card::Inquisitor, Card::Contessa]),
+            ],
+            turn: 1,
+            deck: vec![Card::Duke, Card::Inquisitor, Card::Assassin, Card::Captain],
+            discard_pile: vec![],
+            moves: 0,
+            phase: Phase::Turn,
+            copies: 3,
+            treasury: 46,
+            treasury_reserve: 0,
+            config,
+        };
+        let mut mcts = Mcts::with_config(state.clone(), MctsConfig { iterations: 2_000, ..Default::default() });
+        mcts.search();
+        let bot = MctsBot { tree: Some(mcts), ..Default::default() };
+
+        // a swapped with its inquisitor, we called it and now lose a card
+        let bot_info = |name: &str| OtherBot { name: name.to_string(), coins: 2, cards: 2, allegiance: None };
+        let context = Context {
+            name: "me".to_string(),
+            cards: vec![Card::Duke, Card::Captain],
+            coins: 2,
+            playing_bots: vec![bot_info("me"), bot_info("a")],
+            discard_pile: vec![],
+            history: vec![
+                History::ActionSwapping { by: "a".to_string() },
+                History::ChallengeInquisitor { by: "me".to_string(), target: "a".to_string() },
+                History::ChallengeUnsuccessful { by: "me".to_string(), target: "a".to_string() },
+                History::RevealCard { by: "a".to_string(), card: Card::Inquisitor },
+            ],
+            moves: 1,
+            score: vec![],
+            config,
+            treasury: 46,
+            treasury_reserve: 0,
+            copies: 3,
+        };
+        let mut next = state;
+        next.phase = Phase::LoseCard { player: 0, then: Box::new(Phase::EndTurn) };
+
+        // we end up below our challenge and not in a subtree where nobody challenged
+        let stats = bot.reuse(&next, &context).unwrap().root_stats();
+        assert!(!stats.is_empty());
+        assert!(stats.iter().all(|s| s.player == "me" && matches!(s.mv, Move::Lose(_))));
+    }
 }
@@ -3,7 +3,7 @@
 use rand::{seq::SliceRandom, thread_rng};
 
 use crate::{
-	bot::{BotInterface, Context, OtherBot},
+	bot::{BotInterface, Context},
 	Action, Card,
 };
 
@@ -18,44 +18,44 @@ impl BotInterface for RandomBot {
 		String::from("RandomBot")
 	}
 
-	/// Randomizes all possible [Action]
-	fn on_turn(&self, context: &Context) -> Action {
-		let mut targets = context.playing_bots.clone();
-		targets = targets
-			.iter()
-			.filter(|bot| bot.name != context.name)
-			.cloned()
-			.collect::<Vec<OtherBot>>();
+	/// Randomizes all possible [Action] including the ones of the
+	/// [crate::Reformation] rules the game is played with
+	fn on_turn(&mut self, context: &Context) -> Action {
+		let mut targets = context.targets();
 		targets.shuffle(&mut thread_rng());
+		let target = targets[0].name.clone();
 
-		let mut actions = [
-			Action::Assassination(targets[0].name.clone()),
-			Action::Coup(targets[0].name.clone()),
+		let mut actions = vec![
+			Action::Assassination(target.clone()),
+			Action::Coup(target.clone()),
 			Action::ForeignAid,
 			Action::Swapping,
 			Action::Income,
-			Action::Stealing(targets[0].name.clone()),
+			Action::Stealing(target.clone()),
 			Action::Tax,
 		];
+		if context.config.reformation.factions {
+			let convert = context.playing_bots.choose(&mut thread_rng()).unwrap();
+			actions.push(Action::Conversion(convert.name.clone()));
+			actions.push(Action::Embezzlement);
+		}
+		if context.config.reformation.inquisitor {
+			actions.push(Action::Examine(target));
+		}
 		actions.shuffle(&mut thread_rng());
 		actions[0].clone()
 	}
 
 	/// Randomizes who it coups
-	fn on_auto_coup(&self, context: &Context) -> String {
-		let mut targets = context.playing_bots.clone();
-		targets = targets
-			.iter()
-			.filter(|bot| bot.name != context.name)
-			.cloned()
-			.collect::<Vec<OtherBot>>();
+	fn on_auto_coup(&mut self, context: &Context) -> String {
+		let mut targets = context.targets();
 		targets.shuffle(&mut thread_rng());
 		targets[0].name.clone()
 	}
 
 	/// Randomizes if it challenges or not
 	fn on_challenge_action_round(
-		&self,
+		&mut self,
 		_action: &Action,
 		_by: String,
 		_context: &Context,
@@ -67,7 +67,7 @@ impl BotInterface for RandomBot {
 
 	/// Randomizes if it counters or not
 	fn on_counter(
-		&self,
+		&mut self,
 		_action: &Action,
 		_by: String,
 		_context: &Context,
@@ -79,7 +79,7 @@ impl BotInterface for RandomBot {
 
 	/// Randomizes if it counter-challenges or not
 	fn on_challenge_counter_round(
-		&self,
+		&mut self,
 		_action: &Action,
 		_by: String,
 		_context: &Context,
@@ -91,7 +91,7 @@ impl BotInterface for RandomBot {
 
 	/// Randomizes what card it swaps
 	fn on_swapping_cards(
-		&self,
+		&mut self,
 		new_cards: [Card; 2],
 		context: &Context,
 	) -> [Card; 2] {
@@ -102,8 +102,42 @@ impl BotInterface for RandomBot {
 		[all_visible_cards[0], all_visible_cards[1]]
 	}
 
+	/// Randomizes what card it gives back
+	fn on_exchanging_card(&mut self, new_card: Card, context: &Context) -> Card {
+		let mut all_visible_cards = context.cards.clone();
+		all_visible_cards.push(new_card);
+		*all_visible_cards.choose(&mut thread_rng()).unwrap()
+	}
+
+	/// Randomizes what card it shows
+	fn on_examine(&mut self, _by: String, context: &Context) -> Card {
+		*context.cards.choose(&mut thread_rng()).unwrap()
+	}
+
+	/// Randomizes if it makes the other bot exchange its card
+	fn on_examined_card(
+		&mut self,
+		_card: Card,
+		_target: String,
+		_context: &Context,
+	) -> bool {
+		let mut exchange = [true, false];
+		exchange.shuffle(&mut thread_rng());
+		exchange[0]
+	}
+
+	/// Randomizes what cards it keeps
+	fn on_starting_cards(
+		&mut self,
+		mut dealt_cards: Vec<Card>,
+		_context: &Context,
+	) -> [Card; 2] {
+		dealt_cards.shuffle(&mut thread_rng());
+		[dealt_cards[0], dealt_cards[1]]
+	}
+
 	/// Randomizes what card it discards
-	fn on_card_loss(&self, context: &Context) -> Card {
+	fn on_card_loss(&mut self, context: &Context) -> Card {
 		let mut cards = context.cards.clone();
 		cards.shuffle(&mut thread_rng());
 		cards[0]
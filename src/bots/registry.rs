@@ -0,0 +1,157 @@
+//! A registry of bots so bots can be looked up and built by name.
+//!
+//! ```rust
+//! use coup::{bot::factory, bots::{BotEntry, BotRegistry, StaticBot}, Coup};
+//!
+//! let mut registry = BotRegistry::default();
+//! registry.register(BotEntry::new(
+//!     "Kate",
+//!     "Only ever takes income",
+//!     factory(|| StaticBot),
+//! ));
+//!
+//! let coup_game =
+//!     Coup::new_from_registry(&registry, &["Kate", "HonestBot"], 42).unwrap();
+//! ```
+
+use crate::{
+	bot::{factory, BotFactory, BotInterface},
+	bots::{DuelBot, HonestBot, HumanBot, MctsBot, RandomBot, StaticBot},
+};
+
+/// A bot in the [BotRegistry].
+#[derive(Clone)]
+pub struct BotEntry {
+	/// The name to look the bot up by
+	pub name: String,
+	/// A short description of how the bot plays
+	pub description: String,
+	/// Builds a new instance of the bot
+	pub factory: BotFactory,
+	/// Whether the engine should build a new instance of the bot for each game
+	/// so no state is carried from one game to the next
+	pub fresh_per_game: bool,
+}
+
+impl BotEntry {
+	/// Create a new entry that uses the same instance for all games.
+	pub fn new(name: &str, description: &str, factory: BotFactory) -> Self {
+		Self {
+			name: String::from(name),
+			description: String::from(description),
+			factory,
+			fresh_per_game: false,
+		}
+	}
+
+	/// Build a new instance of the bot for each game.
+	pub fn fresh_per_game(mut self) -> Self {
+		self.fresh_per_game = true;
+		self
+	}
+}
+
+/// A list of bots that can be looked up by name.
+/// The default registry contains all pre-built bots.
+#[derive(Clone)]
+pub struct BotRegistry {
+	entries: Vec<BotEntry>,
+}
+
+impl Default for BotRegistry {
+	fn default() -> Self {
+		let mut registry = Self::new();
+		registry.register(BotEntry::new(
+			"StaticBot",
+			"Only takes income and never challenges or counters",
+			factory(|| StaticBot),
+		));
+		registry.register(BotEntry::new(
+			"HonestBot",
+			"Acts on its own cards and never bluffs",
+			factory(|| HonestBot),
+		));
+		registry.register(BotEntry::new(
+			"RandomBot",
+			"Picks every move at random",
+			factory(|| RandomBot),
+		));
+		registry.register(BotEntry::new(
+			"DuelBot",
+			"Tracks what the opponent claims and is tuned for one on one games",
+			factory(DuelBot::default),
+		));
+		registry.register(BotEntry::new(
+			"MCTSBot",
+			"Picks its actions with a Monte Carlo tree search",
+			factory(MctsBot::new),
+		));
+		registry.register(BotEntry::new(
+			"HumanBot",
+			"Asks you for each decision in the terminal",
+			factory(|| HumanBot::new("You")),
+		));
+		registry
+	}
+}
+
+impl BotRegistry {
+	/// Create an empty registry.
+	pub fn new() -> Self {
+		Self { entries: vec![] }
+	}
+
+	/// Add a bot to the registry.
+	/// A bot with the same name is replaced.
+	pub fn register(&mut self, entry: BotEntry) {
+		match self
+			.entries
+			.iter_mut()
+			.find(|existing| existing.name.eq_ignore_ascii_case(&entry.name))
+		{
+			Some(existing) => *existing = entry,
+			None => self.entries.push(entry),
+		}
+	}
+
+	/// Look up a bot by its name ignoring the case.
+	pub fn get(&self, name: &str) -> Option<&BotEntry> {
+		self.entries.iter().find(|entry| entry.name.eq_ignore_ascii_case(name))
+	}
+
+	/// Build a new instance of a bot by its name.
+	pub fn create(&self, name: &str) -> Option<Box<dyn BotInterface>> {
+		self.get(name).map(|entry| (entry.factory)())
+	}
+
+	/// All bots in the order they were registered.
+	pub fn entries(&self) -> &[BotEntry] {
+		&self.entries
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_registry() {
+		let mut registry = BotRegistry::default();
+		assert_eq!(registry.get("mctsbot").unwrap().name, "MCTSBot");
+		assert_eq!(registry.create("HonestBot").unwrap().get_name(), "HonestBot");
+		assert!(registry.get("Kate").is_none());
+
+		let count = registry.entries().len();
+		registry.register(
+			BotEntry::new("Kate", "Kate", factory(|| StaticBot)).fresh_per_game(),
+		);
+		registry.register(BotEntry::new(
+			"staticbot",
+			"Replaced",
+			factory(|| StaticBot),
+		));
+		assert_eq!(registry.entries().len(), count + 1);
+		assert!(registry.get("kate").unwrap().fresh_per_game);
+		assert_eq!(registry.get("StaticBot").unwrap().description, "Replaced");
+	}
+}
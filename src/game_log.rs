@@ -0,0 +1,209 @@
+//! Machine readable logging of games.
+//!
+//! The engine sends a [LogEvent] to each [LogSink] you add to a game for its
+//! setup, every [History] event, every state change and the final result.
+//! Use the [JsonLinesSink] to write a game as [JSON Lines](https://jsonlines.org/)
+//! to a file or any other writer:
+//!
+//! ```rust
+//! use coup::{bots::StaticBot, game_log::JsonLinesSink, Coup};
+//!
+//! let mut coup_game = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+//! coup_game.add_log_sink(Box::new(JsonLinesSink::new(std::io::sink())));
+//! coup_game.looping(5);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::{
+	fmt,
+	io::{self, Write},
+	sync::{Arc, Mutex},
+};
+
+use crate::{Allegiance, Card, History, Score};
+
+/// The public state of a bot at a point in the game including its cards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Seat {
+	/// The name of the bot used to identify it
+	pub name: String,
+	/// The amount of coins this bot has
+	pub coins: u8,
+	/// The [Card] this bot still has
+	pub cards: Vec<Card>,
+	/// The faction of this bot when playing with the factions of the
+	/// [crate::Reformation] rules
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub allegiance: Option<Allegiance>,
+}
+
+impl fmt::Display for Seat {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		crate::fmt_bot(f, &self.name, self.cards.len(), self.coins, self.allegiance)
+	}
+}
+
+/// Everything that is logged about a game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LogEvent {
+	/// A new game was set up with the seats in the order they play.
+	Setup {
+		/// The number of the game within this [crate::Coup] instance starting at 0
+		round: u64,
+		/// The seed this game was played with
+		seed: u64,
+		/// All bots in this game with their starting hands and coins
+		seats: Vec<Seat>,
+	},
+	/// Something happened in the game that was recorded in the history.
+	Event {
+		/// The event as it is passed to bots in [crate::bot::Context::history]
+		event: History,
+	},
+	/// The state of the table changed.
+	State {
+		/// All bots in this game in the order they play
+		seats: Vec<Seat>,
+		/// The amount of cards left in the deck
+		deck: usize,
+		/// All discarded cards so far
+		discard_pile: Vec<Card>,
+	},
+	/// The game ended.
+	Result {
+		/// The bots still in the game at the end
+		winners: Vec<String>,
+		/// The amount of moves played
+		moves: usize,
+		/// The score after this game
+		score: Score,
+	},
+}
+
+/// A sink receives every [LogEvent] of each game played.
+pub trait LogSink {
+	/// Called for each [LogEvent] in the order they happen.
+	fn log(&mut self, event: &LogEvent);
+}
+
+/// Writes each [LogEvent] as a single line of JSON.
+pub struct JsonLinesSink<W: Write> {
+	writer: W,
+	error: Option<io::Error>,
+}
+
+impl<W: Write> JsonLinesSink<W> {
+	/// Create a new sink writing to the writer you pass in.
+	pub fn new(writer: W) -> Self {
+		Self {
+			writer,
+			error: None,
+		}
+	}
+
+	/// The first error we ran into while writing.
+	/// We stop writing after the first error so the game can finish.
+	pub fn error(&self) -> Option<&io::Error> {
+		self.error.as_ref()
+	}
+}
+
+impl<W: Write> LogSink for JsonLinesSink<W> {
+	fn log(&mut self, event: &LogEvent) {
+		if self.error.is_some() {
+			return;
+		}
+
+		let result = serde_json::to_writer(&mut self.writer, event)
+			.map_err(io::Error::from)
+			.and_then(|_| self.writer.write_all(b"\n"))
+			.and_then(|_| match event {
+				// Flushing once per game so buffered writers stay fast
+				LogEvent::Result { .. } => self.writer.flush(),
+				_ => Ok(()),
+			});
+
+		if let Err(error) = result {
+			self.error = Some(error);
+		}
+	}
+}
+
+/// Keeps all [LogEvent] in memory.
+/// Clones share the same events so you can keep a clone around to read them
+/// after passing the sink to the game.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+	events: Arc<Mutex<Vec<LogEvent>>>,
+}
+
+impl MemorySink {
+	/// Create a new empty sink.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// All events logged so far.
+	pub fn events(&self) -> Vec<LogEvent> {
+		self.events.lock().unwrap().clone()
+	}
+}
+
+impl LogSink for MemorySink {
+	fn log(&mut self, event: &LogEvent) {
+		self.events.lock().unwrap().push(event.clone());
+	}
+}
+
+/// Read back a game log written by the [JsonLinesSink].
+pub fn read_json_lines<R: io::BufRead>(reader: R) -> io::Result<Vec<LogEvent>> {
+	reader
+		.lines()
+		.filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+		.map(|line| {
+			line.and_then(|line| serde_json::from_str(&line).map_err(io::Error::from))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_json_lines_sink() {
+		let events = vec![
+			LogEvent::Setup {
+				round: 0,
+				seed: 42,
+				seats: vec![Seat {
+					name: String::from("StaticBot"),
+					coins: 2,
+					cards: vec![Card::Duke, Card::Contessa],
+					allegiance: None,
+				}],
+			},
+			LogEvent::Event {
+				event: History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Duke,
+				},
+			},
+			LogEvent::Result {
+				winners: vec![String::from("StaticBot")],
+				moves: 1,
+				score: vec![(String::from("StaticBot"), 1.0)],
+			},
+		];
+
+		let mut sink = JsonLinesSink::new(Vec::new());
+		events.iter().for_each(|event| sink.log(event));
+		assert!(sink.error().is_none());
+
+		let output = String::from_utf8(sink.writer).unwrap();
+		assert_eq!(output.lines().count(), 3);
+		assert!(output.starts_with("{\"type\":\"setup\",\"round\":0,\"seed\":42,"));
+		assert_eq!(read_json_lines(output.as_bytes()).unwrap(), events);
+	}
+}
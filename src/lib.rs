@@ -26,18 +26,26 @@
 extern crate cfonts;
 
 use cfonts::{render, Colors, Options};
-use rand::{seq::SliceRandom, thread_rng};
+use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
+pub mod belief;
 pub mod bot;
 pub mod bots;
+pub mod game_log;
 pub mod mcts;
+pub mod replay;
+pub mod tournament;
 
-
-use crate::bot::{BotInterface, Context, OtherBot};
+use crate::{
+	bot::{BotFactory, BotInterface, Context, OtherBot},
+	bots::BotRegistry,
+	game_log::{LogEvent, LogSink, Seat},
+};
 
 /// One of the five cards you get in the game of Coup.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Card {
 	/// - [Action::Swapping] – Draw two character cards from the deck, choose which (if any) to exchange with your cards, then return two<br>
 	/// - [Counter::Stealing] – Block someone from stealing coins from you
@@ -52,10 +60,180 @@ pub enum Card {
 	/// - [Action::Tax] – Take three coins from the treasury<br>
 	/// - [Counter::ForeignAid] – Block someone from taking foreign aid
 	Duke,
+	/// Replaces the [Card::Ambassador] when playing with the Inquisitor of the
+	/// [Reformation] rules.
+	/// - [Action::Swapping] – Draw one character card from the deck and choose whether to exchange it with one of your cards<br>
+	/// - [Action::Examine] – Look at one card of another player and choose whether they have to exchange it<br>
+	/// - [Counter::Stealing] – Block someone from stealing coins from you
+	Inquisitor,
+}
+
+/// The two factions of the [Reformation] rules.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Allegiance {
+	/// Loyal to the old order
+	Loyalist,
+	/// Fighting for the reformation
+	Reformist,
+}
+
+impl Allegiance {
+	/// The other faction.
+	pub fn other(self) -> Self {
+		match self {
+			Allegiance::Loyalist => Allegiance::Reformist,
+			Allegiance::Reformist => Allegiance::Loyalist,
+		}
+	}
+}
+
+/// The rules of the Reformation expansion you can opt into with
+/// [Coup::set_reformation].
+/// Everything is off by default which is the base game.
+#[derive(
+	Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct Reformation {
+	/// Each bot is either a [Allegiance::Loyalist] or a [Allegiance::Reformist],
+	/// alternating around the table.
+	/// Bots can't coup, assassinate, steal from, examine or block the actions of
+	/// bots of their own faction unless everyone is on the same side.
+	/// Adds [Action::Conversion] and [Action::Embezzlement] which pay into and
+	/// take from the Treasury Reserve.
+	pub factions: bool,
+	/// Play with the [Card::Inquisitor] instead of the [Card::Ambassador].
+	pub inquisitor: bool,
+}
+
+impl Reformation {
+	/// All rules of the Reformation expansion.
+	pub fn all() -> Self {
+		Self {
+			factions: true,
+			inquisitor: true,
+		}
+	}
+
+	/// The five cards that are in the deck with these rules.
+	pub fn cards(&self) -> [Card; 5] {
+		[
+			self.exchange_card(),
+			Card::Assassin,
+			Card::Captain,
+			Card::Contessa,
+			Card::Duke,
+		]
+	}
+
+	/// The card you claim for [Action::Swapping] which together with the
+	/// [Card::Captain] also blocks [Action::Stealing].
+	pub fn exchange_card(&self) -> Card {
+		if self.inquisitor {
+			Card::Inquisitor
+		} else {
+			Card::Ambassador
+		}
+	}
+}
+
+/// The numbers and rules a game is played with.
+/// The defaults are the official rules so you only change what your house
+/// rules do differently.
+///
+/// ```rust
+/// use coup::{bots::HonestBot, Coup, GameConfig};
+///
+/// let mut coup_game = Coup::new(vec![Box::new(HonestBot), Box::new(HonestBot)]);
+/// coup_game.set_config(GameConfig {
+///     starting_coins: 3,
+///     max_moves: 500,
+///     ..GameConfig::default()
+/// });
+/// coup_game.play();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameConfig {
+	/// The coins each bot starts a game with.
+	pub starting_coins: u8,
+	/// The coins the bot that goes first starts with instead of the
+	/// `starting_coins`, e.g. 1 in the official two-player variant.
+	pub first_player_coins: Option<u8>,
+	/// How many cards each bot is dealt to keep two of, e.g. 5 in the official
+	/// two-player variant.
+	/// See [BotInterface::on_starting_cards].
+	pub dealt_cards: usize,
+	/// With this many coins a bot has to [Action::Coup].
+	pub auto_coup_coins: u8,
+	/// What an [Action::Coup] costs.
+	pub coup_cost: u8,
+	/// What an [Action::Assassination] costs.
+	pub assassination_cost: u8,
+	/// The most coins an [Action::Stealing] takes.
+	pub stealing_amount: u8,
+	/// The coins an [Action::Tax] takes from the treasury.
+	pub tax_amount: u8,
+	/// The coins in the treasury at the start of a game, 50 in the physical
+	/// game.
+	/// Bots are paid out of the treasury and pay into it so when it runs low
+	/// [Action::Income], [Action::ForeignAid] and [Action::Tax] only take what's
+	/// left.
+	pub treasury_coins: u8,
+	/// After this many moves the game ends in a stalemate and every bot still in
+	/// the game wins.
+	pub max_moves: usize,
+	/// The most bots seated at a table for a game, between 2 and 10.
+	/// Tables of more than 6 bots play with 5 copies of each card instead of 3.
+	pub table_size: usize,
+	/// The rules of the Reformation expansion.
+	pub reformation: Reformation,
+}
+
+impl Default for GameConfig {
+	fn default() -> Self {
+		Self {
+			starting_coins: 2,
+			first_player_coins: None,
+			dealt_cards: 2,
+			auto_coup_coins: 10,
+			coup_cost: 7,
+			assassination_cost: 3,
+			stealing_amount: 2,
+			tax_amount: 3,
+			treasury_coins: 50,
+			max_moves: 1_000,
+			table_size: 6,
+			reformation: Reformation::default(),
+		}
+	}
+}
+
+impl GameConfig {
+	/// The official two-player variant.
+	/// Two bots are seated, the bot that goes first starts with 1 coin and both
+	/// pick their two cards out of five.
+	///
+	/// ```rust
+	/// use coup::{bots::DuelBot, Coup, GameConfig};
+	///
+	/// let mut coup_game = Coup::new(vec![
+	///     Box::new(DuelBot::default()),
+	///     Box::new(DuelBot::default()),
+	/// ]);
+	/// coup_game.set_config(GameConfig::two_player());
+	/// coup_game.play();
+	/// ```
+	pub fn two_player() -> Self {
+		Self {
+			first_player_coins: Some(1),
+			dealt_cards: 5,
+			table_size: 2,
+			..Self::default()
+		}
+	}
 }
 
 /// Actions that can we taken with a [Card] you have.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Action {
 	/// Take this action with your [Card::Assassin].
 	Assassination(String),
@@ -64,7 +242,8 @@ pub enum Action {
 	Coup(String),
 	/// This standard action can be taken at any time.
 	ForeignAid,
-	/// Take this action with your [Card::Ambassador].
+	/// Take this action with your [Card::Ambassador] or your [Card::Inquisitor]
+	/// when playing with the Inquisitor.
 	Swapping,
 	/// This standard action can be taken at any time.
 	Income,
@@ -72,17 +251,27 @@ pub enum Action {
 	Stealing(String),
 	/// Take this action with your [Card::Duke].
 	Tax,
+	/// This standard action of the [Reformation] rules can be taken at any time.
+	/// Pay 1 coin into the Treasury Reserve to change your own allegiance or 2
+	/// coins to change the allegiance of another bot.
+	Conversion(String),
+	/// Take all coins from the Treasury Reserve of the [Reformation] rules by
+	/// claiming you don't have the [Card::Duke].
+	Embezzlement,
+	/// Take this action with your [Card::Inquisitor].
+	Examine(String),
 }
 
 /// Counters are played if something happens that can be countered with a
 /// [Card] you have.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Counter {
 	/// Block an assassination with your [Card::Contessa].
 	Assassination,
 	/// Block foreign aid with your [Card::Duke].
 	ForeignAid,
-	/// Block stealing with your [Card::Captain] or your [Card::Ambassador].
+	/// Block stealing with your [Card::Captain] or your [Card::Ambassador]
+	/// ([Card::Inquisitor] when playing with the Inquisitor).
 	Stealing,
 }
 
@@ -92,7 +281,7 @@ enum ChallengeRound {
 }
 
 /// A collection on all possible moves in the game for bots to analyze.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum History {
 	/// A bot played an Assassin to assassinate another bot for 3 coins.
 	ActionAssassination { by: String, target: String },
@@ -108,6 +297,14 @@ pub enum History {
 	ActionStealing { by: String, target: String },
 	/// A bot played a Duke to take 3 coins of tax from the treasury.
 	ActionTax { by: String },
+	/// A bot paid into the Treasury Reserve to change the allegiance of the
+	/// target which may be itself.
+	ActionConversion { by: String, target: String },
+	/// A bot claimed not to have the Duke to take all coins from the Treasury
+	/// Reserve.
+	ActionEmbezzlement { by: String },
+	/// A bot played an Inquisitor to examine a card of another bot.
+	ActionExamine { by: String, target: String },
 
 	/// A bot challenged another bot for having the Assassin.
 	ChallengeAssassin { by: String, target: String },
@@ -117,6 +314,10 @@ pub enum History {
 	ChallengeCaptain { by: String, target: String },
 	/// A bot challenged another bot for having the Duke.
 	ChallengeDuke { by: String, target: String },
+	/// A bot challenged another bot for having the Inquisitor.
+	ChallengeInquisitor { by: String, target: String },
+	/// A bot challenged another bot for embezzling while having the Duke.
+	ChallengeEmbezzlement { by: String, target: String },
 
 	/// Another bot was trying to assassinated so this bot played the Contessa to counter.
 	CounterAssassination { by: String, target: String },
@@ -131,6 +332,34 @@ pub enum History {
 	CounterChallengeDuke { by: String, target: String },
 	/// Another bot countered with the Captain or Ambassador and this bot challenged it for having that card.
 	CounterChallengeCaptainAmbassedor { by: String, target: String },
+	/// Another bot countered with the Captain or Inquisitor and this bot challenged it for having that card.
+	CounterChallengeCaptainInquisitor { by: String, target: String },
+
+	/// This bot challenged an action and won because the other bot didn't have the card.
+	ChallengeSuccessful { by: String, target: String },
+	/// This bot challenged an action and lost because the other bot had the card.
+	ChallengeUnsuccessful { by: String, target: String },
+	/// This bot challenged a counter and won because the other bot didn't have the card.
+	CounterChallengeSuccessful { by: String, target: String },
+	/// This bot challenged a counter and lost because the other bot had the card.
+	CounterChallengeUnsuccessful { by: String, target: String },
+
+	/// A bot revealed a card to prove it had it after being challenged.
+	RevealCard { by: String, card: Card },
+	/// A bot shuffled its revealed card back into the deck and drew a new one.
+	SwapCard { by: String },
+	/// A bot lost an influence and the card is now on the discard pile.
+	LoseInfluence { by: String, card: Card },
+	/// A bot exchanged cards with the deck through the Ambassador. The cards it
+	/// kept or returned stay hidden.
+	ExchangeCards { by: String },
+	/// A bot was penalized by the engine for an illegal move and loses a card.
+	Penalty { by: String, reason: String },
+	/// The target showed one of its cards to the bot that examined it. The card
+	/// stays hidden to everyone else.
+	ExamineCard { by: String, target: String },
+	/// The bot that examined the target made it exchange the card it showed.
+	ForceExchange { by: String, target: String },
 }
 
 /// The score of the game for all bots.
@@ -140,7 +369,11 @@ struct Bot {
 	name: String,
 	coins: u8,
 	cards: Vec<Card>,
+	// Only set when playing with the factions of the Reformation rules
+	allegiance: Option<Allegiance>,
 	interface: Box<dyn BotInterface>,
+	// Bots that need a new instance for each game are rebuilt from this
+	factory: Option<BotFactory>,
 }
 
 impl fmt::Debug for Bot {
@@ -150,12 +383,13 @@ impl fmt::Debug for Bot {
 			writeln!(f, "  name: {:?}", self.name)?;
 			writeln!(f, "  coins: {:?}", self.coins)?;
 			writeln!(f, "  cards: {:?}", self.cards)?;
+			writeln!(f, "  allegiance: {:?}", self.allegiance)?;
 			write!(f, "}}")
 		} else {
 			write!(
 				f,
-				"Bot {{ name: {:?}, coins: {:?}, cards: {:?} }}",
-				self.name, self.coins, self.cards
+				"Bot {{ name: {:?}, coins: {:?}, cards: {:?}, allegiance: {:?} }}",
+				self.name, self.coins, self.cards, self.allegiance
 			)
 		}
 	}
@@ -163,40 +397,276 @@ impl fmt::Debug for Bot {
 
 impl fmt::Display for Bot {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		write!(
-			f,
-			"\x1b[33m[\x1b[1m{}\x1b[0m \x1b[31m{}{}\x1b[33m 💰{}]\x1b[39m",
-			self.name,
-			"♥".repeat(self.cards.len()),
-			"♡".repeat(2 - self.cards.len()),
-			self.coins
-		)
+		fmt_bot(f, &self.name, self.cards.len(), self.coins, self.allegiance)
 	}
 }
 
+// Bots are rendered the same way in the engine and in a replay
+fn fmt_bot(
+	f: &mut fmt::Formatter<'_>,
+	name: &str,
+	cards: usize,
+	coins: u8,
+	allegiance: Option<Allegiance>,
+) -> fmt::Result {
+	write!(
+		f,
+		"\x1b[33m[\x1b[1m{}\x1b[0m \x1b[31m{}{}\x1b[33m 💰{}{}]\x1b[39m",
+		name,
+		"♥".repeat(cards),
+		"♡".repeat(2_usize.saturating_sub(cards)),
+		coins,
+		match allegiance {
+			Some(Allegiance::Loyalist) => " 👑",
+			Some(Allegiance::Reformist) => " 📜",
+			None => "",
+		}
+	)
+}
+
+// The log line for the last event in the history.
+// Outcomes of challenges look back in the history for the card that was
+// challenged.
+fn describe_event(
+	history: &[History],
+	seat: impl Fn(&str) -> Seat,
+) -> Option<String> {
+	let challenged_cards = || {
+		history.iter().rev().find_map(|event| match event {
+			History::ChallengeAssassin { .. } => Some("Assassin"),
+			History::ChallengeAmbassador { .. } => Some("Ambassador"),
+			History::ChallengeCaptain { .. } => Some("Captain"),
+			History::ChallengeDuke { .. } => Some("Duke"),
+			History::ChallengeInquisitor { .. } => Some("Inquisitor"),
+			History::ChallengeEmbezzlement { .. } => Some("Duke"),
+			History::CounterChallengeContessa { .. } => Some("Contessa"),
+			History::CounterChallengeDuke { .. } => Some("Duke"),
+			History::CounterChallengeCaptainAmbassedor { .. } => {
+				Some("Captain or the Ambassador")
+			},
+			History::CounterChallengeCaptainInquisitor { .. } => {
+				Some("Captain or the Inquisitor")
+			},
+			_ => None,
+		})
+	};
+
+	let embezzled = || {
+		matches!(
+			history.iter().rev().find(|event| matches!(
+				event,
+				History::ChallengeAssassin { .. }
+					| History::ChallengeAmbassador { .. }
+					| History::ChallengeCaptain { .. }
+					| History::ChallengeDuke { .. }
+					| History::ChallengeInquisitor { .. }
+					| History::ChallengeEmbezzlement { .. }
+			)),
+			Some(History::ChallengeEmbezzlement { .. })
+		)
+	};
+
+	Some(match history.last()? {
+		History::ActionAssassination { by, target } => format!(
+			"🃏  {} assassinates {} with the \x1b[33mAssassin\x1b[39m",
+			seat(by),
+			seat(target)
+		),
+		History::ActionCoup { by, target } => {
+			format!("🃏  {} \x1b[33mcoups\x1b[39m {}", seat(by), seat(target))
+		},
+		History::ActionForeignAid { by } => {
+			format!("🃏  {} takes \x1b[33mforeign aid\x1b[39m", seat(by))
+		},
+		History::ActionSwapping { by } => format!(
+			"🃏  {} swaps cards with \x1b[33mthe Ambassador\x1b[39m",
+			seat(by)
+		),
+		History::ActionIncome { by } => {
+			format!("🃏  {} takes \x1b[33ma coin\x1b[39m", seat(by))
+		},
+		History::ActionStealing { by, target } => format!(
+			"🃏  {} \x1b[33msteals 2 coins\x1b[39m from {}",
+			seat(by),
+			seat(target)
+		),
+		History::ActionTax { by } => {
+			format!("🃏  {} takes tax with the \x1b[33mDuke\x1b[39m", seat(by))
+		},
+		History::ActionConversion { by, target } if by == target => {
+			format!("🃏  {} \x1b[33mconverts\x1b[39m to the other side", seat(by))
+		},
+		History::ActionConversion { by, target } => format!(
+			"🃏  {} \x1b[33mconverts\x1b[39m {} to the other side",
+			seat(by),
+			seat(target)
+		),
+		History::ActionEmbezzlement { by } => format!(
+			"🃏  {} \x1b[33membezzles\x1b[39m the Treasury Reserve",
+			seat(by)
+		),
+		History::ActionExamine { by, target } => format!(
+			"🃏  {} examines {} with the \x1b[33mInquisitor\x1b[39m",
+			seat(by),
+			seat(target)
+		),
+		History::ChallengeAssassin { by, target }
+		| History::ChallengeAmbassador { by, target }
+		| History::ChallengeCaptain { by, target }
+		| History::ChallengeDuke { by, target }
+		| History::ChallengeInquisitor { by, target }
+		| History::ChallengeEmbezzlement { by, target }
+		| History::CounterChallengeContessa { by, target }
+		| History::CounterChallengeDuke { by, target }
+		| History::CounterChallengeCaptainAmbassedor { by, target }
+		| History::CounterChallengeCaptainInquisitor { by, target } => {
+			format!("❓  {} was challenged by {}", seat(target), seat(by))
+		},
+		History::CounterAssassination { by, target }
+		| History::CounterForeignAid { by, target }
+		| History::CounterStealing { by, target } => {
+			format!("🛑  {} was countered by {}", seat(target), seat(by))
+		},
+		// Embezzling is a claim not to have the Duke so it's the other way around
+		History::ChallengeSuccessful { target, .. } if embezzled() => format!(
+			"👍  The challenge was successful because {} \x1b[33mdid have the Duke\x1b[39m",
+			seat(target)
+		),
+		History::ChallengeUnsuccessful { target, .. } if embezzled() => format!(
+			"👎  The challenge was unsuccessful because {} \x1b[33mdidn't have the Duke\x1b[39m",
+			seat(target)
+		),
+		History::ChallengeSuccessful { target, .. } => format!(
+			"👍  The challenge was successful because {} \x1b[33mdidn't have the {}\x1b[39m",
+			seat(target),
+			challenged_cards()?
+		),
+		History::ChallengeUnsuccessful { target, .. } => format!(
+			"👎  The challenge was unsuccessful because {} \x1b[33mdid have the {}\x1b[39m",
+			seat(target),
+			challenged_cards()?
+		),
+		History::CounterChallengeSuccessful { target, .. } => format!(
+			"👍  The counter was successful because {} \x1b[33mdidn't have the {}\x1b[39m",
+			seat(target),
+			challenged_cards()?
+		),
+		History::CounterChallengeUnsuccessful { target, .. } => format!(
+			"👎  The counter was unsuccessful because {} \x1b[33mdid have the {}\x1b[39m",
+			seat(target),
+			challenged_cards()?
+		),
+		History::RevealCard { .. } | History::ExchangeCards { .. } => return None,
+		History::SwapCard { by } => format!(
+			"🔄  {} is swapping its card for a new card from the deck",
+			seat(by)
+		),
+		History::LoseInfluence { by, card } => {
+			let seat = seat(by);
+			format!(
+				"{}  {} has lost the \x1b[33m{:?}\x1b[39m",
+				if seat.cards.is_empty() { "☠️ " } else { "💔" },
+				seat,
+				card
+			)
+		},
+		History::Penalty { by, reason } => format!(
+			"🚨  {} is being penalized because \x1b[33m{}\x1b[39m",
+			seat(by),
+			reason
+		),
+		History::ExamineCard { by, target } => {
+			format!("🔍  {} shows a card to {}", seat(target), seat(by))
+		},
+		History::ForceExchange { by, target } => format!(
+			"🔍  {} makes {} exchange the card it showed",
+			seat(by),
+			seat(target)
+		),
+	})
+}
+
 /// The Coup game engine.
 pub struct Coup {
 	bots: Vec<Bot>,
 	playing_bots: Vec<usize>,
+	seats: Vec<usize>,
 	deck: Vec<Card>,
 	discard_pile: Vec<Card>,
 	history: Vec<History>,
 	score: Score,
+	config: GameConfig,
+	treasury: u8,
+	treasury_reserve: u8,
 	turn: usize,
 	moves: usize,
 	log: bool,
+	quiet: bool,
 	rounds: u64,
 	round: u64,
+	seed: u64,
+	seed_rng: StdRng,
+	rng: StdRng,
+	game_seeds: Vec<u64>,
+	log_sinks: Vec<Box<dyn LogSink>>,
 }
 
 impl Coup {
 	/// Start a new Coup game by passing in all your bots in a Vec.
+	/// The seed for this instance is picked at random, use
+	/// [Coup::new_with_seed] if you need to reproduce the games played.
 	pub fn new(user_bots: Vec<Box<dyn BotInterface>>) -> Self {
+		Self::new_with_seed(user_bots, thread_rng().gen())
+	}
+
+	/// Start a new Coup game with a seed.
+	/// Each game played derives its own seed from this seed so running the same
+	/// bots with the same seed will play the exact same games as long as your bots
+	/// don't bring their own randomness.
+	pub fn new_with_seed(
+		user_bots: Vec<Box<dyn BotInterface>>,
+		seed: u64,
+	) -> Self {
+		Self::new_with_factories(
+			user_bots.into_iter().map(|bot| (bot, None)).collect(),
+			seed,
+		)
+	}
+
+	/// Start a new Coup game with the bots you look up by name in the
+	/// [BotRegistry].
+	/// Bots that are registered as fresh per game get a new instance before each
+	/// game.
+	pub fn new_from_registry(
+		registry: &BotRegistry,
+		names: &[&str],
+		seed: u64,
+	) -> Result<Self, String> {
+		let bots = names
+			.iter()
+			.map(|name| {
+				let entry = registry
+					.get(name)
+					.ok_or(format!("There is no bot called \"{}\"", name))?;
+				Ok((
+					(entry.factory)(),
+					entry.fresh_per_game.then(|| entry.factory.clone()),
+				))
+			})
+			.collect::<Result<Vec<_>, String>>()?;
+
+		Ok(Self::new_with_factories(bots, seed))
+	}
+
+	fn new_with_factories(
+		user_bots: Vec<(Box<dyn BotInterface>, Option<BotFactory>)>,
+		seed: u64,
+	) -> Self {
 		let mut bots: Vec<Bot> = Vec::new();
 		let mut existing_names: Vec<String> = Vec::new();
 		let mut score: Vec<(String, f64)> = Vec::new();
 
-		for bot in user_bots.into_iter() {
+		for (bot, factory) in user_bots.into_iter() {
 			let base_name = bot.get_name();
 
 			// Generating a unique name for the bot
@@ -213,7 +683,9 @@ impl Coup {
 				name: unique_name.clone(),
 				coins: 2,
 				cards: Vec::new(),
+				allegiance: None,
 				interface: bot,
+				factory,
 			};
 
 			bots.push(bot);
@@ -223,46 +695,168 @@ impl Coup {
 		Self {
 			bots,
 			playing_bots: vec![],
+			seats: vec![],
 			deck: vec![],
 			discard_pile: vec![],
 			history: vec![],
 			score,
+			config: GameConfig::default(),
+			treasury: GameConfig::default().treasury_coins,
+			treasury_reserve: 0,
 			turn: 0,
 			moves: 0,
 			log: true,
+			quiet: false,
 			round: 0,
 			rounds: 0,
+			seed,
+			seed_rng: StdRng::seed_from_u64(seed),
+			rng: StdRng::seed_from_u64(seed),
+			game_seeds: vec![],
+			log_sinks: vec![],
 		}
 	}
 
+	/// Add a sink that receives a machine readable [LogEvent] for the setup,
+	/// every event, every state change and the result of each game played.
+	/// See [game_log] for the sinks that come with the engine.
+	pub fn add_log_sink(&mut self, sink: Box<dyn LogSink>) {
+		self.log_sinks.push(sink);
+	}
+
+	/// Turn off all console output of [Coup::play] and [Coup::looping].
+	/// Useful when you only care about the score or the log sinks.
+	pub fn set_quiet(&mut self, quiet: bool) {
+		self.quiet = quiet;
+		self.log = !quiet;
+	}
+
+	/// Play all following games with the numbers and rules of this config.
+	/// The table size is kept between 2 and 10.
+	pub fn set_config(&mut self, config: GameConfig) {
+		self.config = GameConfig {
+			table_size: config.table_size.clamp(2, 10),
+			..config
+		};
+	}
+
+	/// The config all games are played with.
+	pub fn get_config(&self) -> &GameConfig {
+		&self.config
+	}
+
+	/// Play all following games with the rules of the Reformation expansion you
+	/// turned on.
+	///
+	/// ```rust
+	/// use coup::{bots::HonestBot, Coup, Reformation};
+	///
+	/// let mut coup_game = Coup::new(vec![Box::new(HonestBot), Box::new(HonestBot)]);
+	/// coup_game.set_reformation(Reformation::all());
+	/// coup_game.play();
+	/// ```
+	pub fn set_reformation(&mut self, reformation: Reformation) {
+		self.config.reformation = reformation;
+	}
+
+	/// Seat up to this many bots at the table for each game, 6 by default.
+	/// Tables of more than 6 bots play with 5 copies of each card instead of 3
+	/// as the official rules suggest.
+	/// The size is kept between 2 and 10.
+	///
+	/// ```rust
+	/// use coup::{bots::StaticBot, Coup};
+	///
+	/// let mut coup_game = Coup::new((0..8).map(|_| Box::new(StaticBot) as _).collect());
+	/// coup_game.set_table_size(8);
+	/// coup_game.play();
+	/// ```
+	pub fn set_table_size(&mut self, table_size: usize) {
+		self.config.table_size = table_size.clamp(2, 10);
+	}
+
+	/// The score of all games played so far.
+	pub fn get_score(&self) -> &Score {
+		&self.score
+	}
+
+	/// The seed this instance was created with.
+	pub fn get_seed(&self) -> u64 {
+		self.seed
+	}
+
+	/// The seeds of each game played so far in the order they were played.
+	/// Pass any of them to [Coup::replay] to play that game again.
+	pub fn get_game_seeds(&self) -> &[u64] {
+		&self.game_seeds
+	}
+
 	/// A public method to get a new deck.
 	/// This can be used by bots to make sure you get the same amount of cards as
 	/// the engine does.
 	pub fn new_deck() -> Vec<Card> {
-		let mut deck = vec![
-			Card::Ambassador,
-			Card::Ambassador,
-			Card::Ambassador,
-			Card::Assassin,
-			Card::Assassin,
-			Card::Assassin,
-			Card::Captain,
-			Card::Captain,
-			Card::Captain,
-			Card::Contessa,
-			Card::Contessa,
-			Card::Contessa,
-			Card::Duke,
-			Card::Duke,
-			Card::Duke,
-		];
-		deck.shuffle(&mut thread_rng());
+		Self::new_deck_with_rng(&mut thread_rng())
+	}
+
+	/// The same as [Coup::new_deck] but shuffled with the random number generator
+	/// you pass in.
+	pub fn new_deck_with_rng<R: Rng + ?Sized>(rng: &mut R) -> Vec<Card> {
+		Self::new_table_deck_with_rng(&Reformation::default(), 6, rng)
+	}
+
+	/// The same as [Coup::new_deck_with_rng] but with the cards of the
+	/// [Reformation] rules you pass in and enough copies of each for a table of
+	/// `players`.
+	pub fn new_table_deck_with_rng<R: Rng + ?Sized>(
+		reformation: &Reformation,
+		players: usize,
+		rng: &mut R,
+	) -> Vec<Card> {
+		let copies = Self::copies_per_card(players);
+		let mut deck = reformation
+			.cards()
+			.iter()
+			.flat_map(|card| vec![*card; copies])
+			.collect::<Vec<Card>>();
+		deck.shuffle(rng);
 		deck
 	}
 
+	/// How many copies of each card are in the deck of a table of `players`.
+	pub fn copies_per_card(players: usize) -> usize {
+		if players > 6 {
+			5
+		} else {
+			3
+		}
+	}
+
 	fn setup(&mut self) {
-		// A fresh deck
-		self.deck = Coup::new_deck();
+		let game_seed = self.seed_rng.gen();
+		self.setup_with_seed(game_seed);
+	}
+
+	fn setup_with_seed(&mut self, game_seed: u64) {
+		// Everything random within a game comes from this seed
+		self.rng = StdRng::seed_from_u64(game_seed);
+
+		// Bots that asked for it get a fresh instance for every game after the first
+		if !self.game_seeds.is_empty() {
+			for bot in self.bots.iter_mut() {
+				if let Some(factory) = &bot.factory {
+					bot.interface = factory();
+				}
+			}
+		}
+		self.game_seeds.push(game_seed);
+
+		// A fresh deck big enough for everyone who gets a seat
+		let players = self.bots.len().min(self.config.table_size);
+		self.deck = Coup::new_table_deck_with_rng(
+			&self.config.reformation,
+			players,
+			&mut self.rng,
+		);
 
 		// Put the index of all bots into play so we can shuffle them later
 		self.playing_bots.clear();
@@ -271,20 +865,89 @@ impl Coup {
 		}
 
 		// Shuffle all bots each round and limit them to the max players per game
-		self.playing_bots.shuffle(&mut thread_rng());
-		self.playing_bots.truncate(6);
-
-		// Give all playing bots cards and coins
-		for bot in self.playing_bots.iter() {
-			let new_cards = vec![self.deck.pop().unwrap(), self.deck.pop().unwrap()];
-			self.bots[*bot].cards = new_cards;
-			self.bots[*bot].coins = 2;
+		self.playing_bots.shuffle(&mut self.rng);
+		self.playing_bots.truncate(self.config.table_size);
+		self.seats.clone_from(&self.playing_bots);
+
+		// Give all playing bots cards and coins, when they're dealt more than two
+		// cards they hold on to the first two until they picked theirs
+		let dealt = self
+			.config
+			.dealt_cards
+			.clamp(2, self.deck.len() / self.playing_bots.len().max(1));
+		self.treasury = self.config.treasury_coins;
+		let mut hands = vec![];
+		for (seat, bot) in self.playing_bots.clone().into_iter().enumerate() {
+			let hand =
+				(0..dealt).map(|_| self.deck.pop().unwrap()).collect::<Vec<Card>>();
+			self.bots[bot].cards = hand[..2].to_vec();
+			let coins = match self.config.first_player_coins {
+				Some(coins) if seat == 0 => coins,
+				_ => self.config.starting_coins,
+			};
+			self.bots[bot].coins = self.take_from_treasury(coins);
+			hands.push(hand);
+		}
+
+		// Factions alternate around the table starting with a Loyalist
+		for bot in self.bots.iter_mut() {
+			bot.allegiance = None;
 		}
+		if self.config.reformation.factions {
+			let mut allegiance = Allegiance::Loyalist;
+			for bot in self.playing_bots.iter() {
+				self.bots[*bot].allegiance = Some(allegiance);
+				allegiance = allegiance.other();
+			}
+		}
+		self.treasury_reserve = 0;
 
 		self.discard_pile = vec![];
 		self.history = vec![];
 		self.turn = 0;
 		self.moves = 0;
+
+		if dealt > 2 {
+			for (bot, hand) in self.playing_bots.clone().into_iter().zip(hands) {
+				self.pick_starting_cards(bot, hand);
+			}
+			self.deck.shuffle(&mut self.rng);
+		}
+	}
+
+	// The bot keeps two of the cards it was dealt and the rest go back into the
+	// deck
+	fn pick_starting_cards(&mut self, bot: usize, hand: Vec<Card>) {
+		// The first two are only held on to until it picked so it has no cards yet
+		let mut context = self.get_context(self.bots[bot].name.clone());
+		context.cards = vec![];
+		let kept =
+			self.bots[bot].interface.on_starting_cards(hand.clone(), &context);
+
+		let mut rest = hand.clone();
+		for card in kept.iter() {
+			match rest.iter().position(|c| c == card) {
+				Some(index) => {
+					rest.remove(index);
+				},
+				// It can't keep cards it wasn't dealt so it gets the first two
+				None => {
+					self.bots[bot].cards = hand[..2].to_vec();
+					self.deck.extend_from_slice(&hand[2..]);
+					return;
+				},
+			}
+		}
+
+		self.bots[bot].cards = kept.to_vec();
+		self.deck.extend(rest);
+	}
+
+	// Pays out as many of the coins as are left in the treasury
+	fn take_from_treasury(&mut self, coins: u8) -> u8 {
+		let coins = coins.min(self.treasury);
+		self.treasury -= coins;
+		coins
 	}
 
 	fn log(message: std::fmt::Arguments, logging: bool) {
@@ -293,10 +956,73 @@ impl Coup {
 		}
 	}
 
+	fn emit(&mut self, event: LogEvent) {
+		for sink in self.log_sinks.iter_mut() {
+			sink.log(&event);
+		}
+	}
+
+	// All bots of this game including the ones that are already out
+	fn get_seats(&self) -> Vec<Seat> {
+		self
+			.seats
+			.iter()
+			.map(|bot_index| self.get_seat(&self.bots[*bot_index].name))
+			.collect()
+	}
+
+	fn get_seat(&self, name: &str) -> Seat {
+		let bot = self.get_bot_by_name(String::from(name));
+		Seat {
+			name: bot.name.clone(),
+			coins: bot.coins,
+			cards: bot.cards.clone(),
+			allegiance: bot.allegiance,
+		}
+	}
+
+	fn emit_state(&mut self) {
+		if !self.log_sinks.is_empty() {
+			let event = LogEvent::State {
+				seats: self.get_seats(),
+				deck: self.deck.len(),
+				discard_pile: self.discard_pile.clone(),
+			};
+			self.emit(event);
+		}
+	}
+
+	// Every event goes into the history and out to all log sinks
+	fn record(&mut self, event: History) {
+		if !self.log_sinks.is_empty() {
+			self.emit(LogEvent::Event {
+				event: event.clone(),
+			});
+		}
+		self.history.push(event);
+
+		let event = self.history.last().unwrap();
+		for bot_index in self.seats.iter() {
+			self.bots[*bot_index].interface.on_event(event);
+		}
+
+		if self.log {
+			if let Some(line) =
+				describe_event(&self.history, |name| self.get_seat(name))
+			{
+				Self::log(format_args!("{}", line), self.log);
+			}
+		}
+	}
+
 	fn get_bot_by_name(&self, name: String) -> &Bot {
 		self.bots.iter().find(|bot| bot.name == name).unwrap()
 	}
 
+	fn get_bot_by_name_mut(&mut self, name: String) -> &mut Bot {
+		self.bots.iter_mut().find(|bot| bot.name == name).unwrap()
+	}
+
 	fn get_other_bots(&self) -> Vec<OtherBot> {
 		self
 			.playing_bots
@@ -307,6 +1033,7 @@ impl Coup {
 					name: bot.name.clone(),
 					coins: bot.coins,
 					cards: bot.cards.len() as u8,
+					allegiance: bot.allegiance,
 				}
 			})
 			.filter(|bot| bot.cards != 0)
@@ -321,7 +1048,12 @@ impl Coup {
 			playing_bots: self.get_other_bots(),
 			discard_pile: self.discard_pile.clone(),
 			history: self.history.clone(),
+			moves: self.moves,
 			score: self.score.clone(),
+			config: self.config,
+			treasury: self.treasury,
+			treasury_reserve: self.treasury_reserve,
+			copies: Coup::copies_per_card(self.seats.len()),
 		}
 	}
 
@@ -331,61 +1063,48 @@ impl Coup {
 			return;
 		}
 		let context = self.get_context(name.clone());
-		self.bots.iter_mut().for_each(|bot| {
-			let context = Context {
-				coins: bot.coins,
-				cards: bot.cards.clone(),
-				..context.clone()
-			};
-			if bot.name == name {
-				let lost_card = bot.interface.on_card_loss(&context);
-				// Bot discarded a card it didn't have so now we kill it dead
-				if !bot.cards.contains(&lost_card) {
-					Self::log(format_args!("🚨  {} is being penalized because \x1b[33mit discarded a card({:?}) it didn't have\x1b[39m", bot, lost_card), self.log);
-
-					let card = bot.cards.pop().unwrap();
-					let mut lost_cards = format!("{:?}", card);
-					self.discard_pile.push(card);
-
-					if !bot.cards.is_empty() {
-						let card = bot.cards.pop().unwrap();
-						lost_cards =
-							format!("{} and {:?}", lost_cards, card);
-						self.discard_pile.push(card);
-					}
+		let index = self.bots.iter().position(|bot| bot.name == name).unwrap();
+		let lost_card = self.bots[index].interface.on_card_loss(&context);
+
+		// Bot discarded a card it didn't have so now we kill it dead
+		if !self.bots[index].cards.contains(&lost_card) {
+			self.record(History::Penalty {
+				by: name.clone(),
+				reason: format!("it discarded a card({:?}) it didn't have", lost_card),
+			});
 
-					bot.cards = vec![];
-					Self::log(format_args!("☠️   {} has lost the \x1b[33m{:?}\x1b[39m", bot, lost_cards), self.log);
-				} else {
-					if let Some(index) = bot.cards.iter().position(|&c| c == lost_card) {
-						bot.cards.remove(index);
-					}
-					self.discard_pile.push(lost_card);
-
-					Self::log(format_args!(
-						"{}  {} has lost the \x1b[33m{:?}\x1b[39m",
-						if bot.cards.is_empty() {
-							"☠️ "
-						} else {
-							"💔"
-						},
-						bot,
-						lost_card
-					), self.log);
-				}
+			while let Some(card) = self.bots[index].cards.pop() {
+				self.discard_pile.push(card);
+				self.record(History::LoseInfluence {
+					by: name.clone(),
+					card,
+				});
 			}
-		});
+		} else {
+			if let Some(card_index) =
+				self.bots[index].cards.iter().position(|&c| c == lost_card)
+			{
+				self.bots[index].cards.remove(card_index);
+			}
+			self.discard_pile.push(lost_card);
+			self.record(History::LoseInfluence {
+				by: name.clone(),
+				card: lost_card,
+			});
+		}
+
+		// An exiled bot gives its coins back to the treasury
+		if self.bots[index].cards.is_empty() {
+			self.treasury += self.bots[index].coins;
+			self.bots[index].coins = 0;
+		}
 	}
 
 	fn penalize_bot(&mut self, name: String, reason: &str) {
-		Self::log(
-			format_args!(
-				"🚨  {} is being penalized because \x1b[33m{}\x1b[39m",
-				self.get_bot_by_name(name.clone()),
-				reason
-			),
-			self.log,
-		);
+		self.record(History::Penalty {
+			by: name.clone(),
+			reason: String::from(reason),
+		});
 		self.card_loss(name);
 	}
 
@@ -398,15 +1117,34 @@ impl Coup {
 			!= 1
 	}
 
+	// With factions a bot can't target its own side unless everyone is on the
+	// same side
+	fn same_faction(&self, by: &str, target: &str) -> bool {
+		let allegiance =
+			|name: &str| self.get_bot_by_name(String::from(name)).allegiance;
+		allegiance(by).is_some()
+			&& allegiance(by) == allegiance(target)
+			&& self.playing_bots.iter().any(|bot| {
+				!self.bots[*bot].cards.is_empty()
+					&& self.bots[*bot].allegiance != allegiance(by)
+			})
+	}
+
 	fn set_score(&mut self, winners: Vec<String>) {
 		let winner_count = winners.len() as f64;
-		let loser_count = if self.bots.len() > 6 {
-			6.0
-		} else {
-			self.bots.len() as f64
-		} - winner_count;
+		let loser_count = self.seats.len() as f64 - winner_count;
+		// A stalemate with everyone still in the game is a draw
+		if loser_count == 0.0 {
+			return;
+		}
 		let loser_score = -1.0 / loser_count;
 		let winner_score = -((loser_score * loser_count) / winner_count);
+		// Bots that didn't get a seat this game neither win nor lose
+		let seated = self
+			.seats
+			.iter()
+			.map(|bot| self.bots[*bot].name.clone())
+			.collect::<Vec<String>>();
 
 		self.score = self
 			.score
@@ -414,8 +1152,10 @@ impl Coup {
 			.map(|(name, score)| {
 				if winners.contains(name) {
 					(name.clone(), score + winner_score)
-				} else {
+				} else if seated.contains(name) {
 					(name.clone(), score + loser_score)
+				} else {
+					(name.clone(), *score)
 				}
 			})
 			.collect::<Score>();
@@ -423,32 +1163,38 @@ impl Coup {
 
 	// We take a card from a bot and replace it with a new one from the deck
 	fn swap_card(&mut self, card: Card, swopee: String) {
-		Self::log(
-			format_args!(
-				"🔄  {} is swapping its card for a new card from the deck",
-				self.get_bot_by_name(swopee.clone())
-			),
-			self.log,
-		);
 		for bot in self.bots.iter_mut() {
 			if bot.name == swopee.clone() {
 				if let Some(index) = bot.cards.iter().position(|&c| c == card) {
 					bot.cards.remove(index);
 				}
 				self.deck.push(card);
-				self.deck.shuffle(&mut thread_rng());
+				self.deck.shuffle(&mut self.rng);
 
 				let mut new_cards = bot.cards.clone();
 				new_cards.push(self.deck.pop().unwrap());
 				bot.cards = new_cards;
 			}
 		}
+		self.record(History::SwapCard { by: swopee });
 	}
 
 	/// Playing a game which means we setup the table, give each bots their cards
 	/// and coins and start the game loop.
 	pub fn play(&mut self) {
 		self.setup();
+		self.run_game();
+	}
+
+	/// Play a single game from a seed recorded by [Coup::get_game_seeds].
+	/// With the same bots in the same order this plays the exact same game again.
+	pub fn replay(&mut self, game_seed: u64) {
+		self.setup_with_seed(game_seed);
+		self.run_game();
+	}
+
+	fn run_game(&mut self) {
+		let game_seed = *self.game_seeds.last().unwrap();
 
 		// Logo
 		let output = render(Options {
@@ -475,12 +1221,29 @@ impl Coup {
 			format_args!("🤺  This rounds player:\n     {}\n", bots.join("\n     "),),
 			self.log,
 		);
+		Self::log(
+			format_args!("🎲  Seed: \x1b[90m{}\x1b[39m\n", game_seed),
+			self.log,
+		);
+		if !self.log_sinks.is_empty() {
+			let event = LogEvent::Setup {
+				round: self.round,
+				seed: game_seed,
+				seats: self.get_seats(),
+			};
+			self.emit(event);
+		}
+
+		for bot_index in self.playing_bots.iter() {
+			let context = self.get_context(self.bots[*bot_index].name.clone());
+			self.bots[*bot_index].interface.on_game_start(&context);
+		}
 
 		// Let's play
 		while self.playing_bots.len() > 1 {
 			self.game_loop();
 
-			if self.moves >= 1000 {
+			if self.moves >= self.config.max_moves {
 				break;
 			}
 		}
@@ -493,6 +1256,21 @@ impl Coup {
 
 		self.set_score(winners.clone());
 
+		let hands = self.get_seats();
+		for bot_index in self.seats.iter() {
+			let context = self.get_context(self.bots[*bot_index].name.clone());
+			self.bots[*bot_index].interface.on_game_end(&winners, &hands, &context);
+		}
+
+		if !self.log_sinks.is_empty() {
+			let event = LogEvent::Result {
+				winners: winners.clone(),
+				moves: self.moves,
+				score: self.score.clone(),
+			};
+			self.emit(event);
+		}
+
 		Self::log(
 			format_args!(
 				"\n 🎉🎉🎉 The winner{} \x1b[1m{}\x1b[0m \x1b[90min {} moves\x1b[39m\n",
@@ -511,7 +1289,9 @@ impl Coup {
 			self.get_context(self.bots[self.playing_bots[self.turn]].name.clone());
 
 		// If you have 10 or more coins you must coup
-		let action = if self.bots[self.playing_bots[self.turn]].coins >= 10 {
+		let action = if self.bots[self.playing_bots[self.turn]].coins
+			>= self.config.auto_coup_coins
+		{
 			let target = self.bots[self.playing_bots[self.turn]]
 				.interface
 				.on_auto_coup(&context);
@@ -527,19 +1307,16 @@ impl Coup {
 						context.name.clone(),
 						"it tried to assassinate an unknown bot",
 					);
+				} else if self.same_faction(&context.name, &target_name) {
+					self.penalize_bot(
+						context.name.clone(),
+						"it tried to assassinate a bot of its own faction",
+					);
 				} else {
-					self.history.push(History::ActionAssassination {
+					self.record(History::ActionAssassination {
 						by: context.name.clone(),
 						target: target_name.clone(),
 					});
-					Self::log(
-						format_args!(
-							"🃏  {} assassinates {} with the \x1b[33mAssassin\x1b[39m",
-							self.bots[self.playing_bots[self.turn]],
-							self.get_bot_by_name(target_name.clone())
-						),
-						self.log,
-					);
 					self.challenge_and_counter_round(
 						Action::Assassination(target_name.clone()),
 						target_name,
@@ -552,59 +1329,35 @@ impl Coup {
 						context.name.clone(),
 						"it tried to coup an unknown bot",
 					);
+				} else if self.same_faction(&context.name, &target_name) {
+					self.penalize_bot(
+						context.name.clone(),
+						"it tried to coup a bot of its own faction",
+					);
 				} else {
-					self.history.push(History::ActionCoup {
+					self.record(History::ActionCoup {
 						by: context.name.clone(),
 						target: target_name.clone(),
 					});
-					Self::log(
-						format_args!(
-							"🃏  {} \x1b[33mcoups\x1b[39m {}",
-							self.bots[self.playing_bots[self.turn]],
-							self.get_bot_by_name(target_name.clone())
-						),
-						self.log,
-					);
 					self.action_couping(target_name.clone());
 				}
 			},
 			Action::ForeignAid => {
-				self.history.push(History::ActionForeignAid {
+				self.record(History::ActionForeignAid {
 					by: context.name.clone(),
 				});
-				Self::log(
-					format_args!(
-						"🃏  {} takes \x1b[33mforeign aid\x1b[39m",
-						self.bots[self.playing_bots[self.turn]],
-					),
-					self.log,
-				);
 				self.counter_round_only();
 			},
 			Action::Swapping => {
-				self.history.push(History::ActionSwapping {
+				self.record(History::ActionSwapping {
 					by: context.name.clone(),
 				});
-				Self::log(
-					format_args!(
-						"🃏  {} swaps cards with \x1b[33mthe Ambassador\x1b[39m",
-						self.bots[self.playing_bots[self.turn]]
-					),
-					self.log,
-				);
 				self.challenge_round_only(Action::Swapping);
 			},
 			Action::Income => {
-				self.history.push(History::ActionIncome {
+				self.record(History::ActionIncome {
 					by: context.name.clone(),
 				});
-				Self::log(
-					format_args!(
-						"🃏  {} takes \x1b[33ma coin\x1b[39m",
-						self.bots[self.playing_bots[self.turn]]
-					),
-					self.log,
-				);
 				self.action_income();
 			},
 			Action::Stealing(target_name) => {
@@ -613,19 +1366,16 @@ impl Coup {
 						context.name.clone(),
 						"it tried to steal from an unknown bot",
 					);
+				} else if self.same_faction(&context.name, &target_name) {
+					self.penalize_bot(
+						context.name.clone(),
+						"it tried to steal from a bot of its own faction",
+					);
 				} else {
-					self.history.push(History::ActionStealing {
+					self.record(History::ActionStealing {
 						by: context.name.clone(),
 						target: target_name.clone(),
 					});
-					Self::log(
-						format_args!(
-							"🃏  {} \x1b[33msteals 2 coins\x1b[39m from {}",
-							self.bots[self.playing_bots[self.turn]],
-							self.get_bot_by_name(target_name.clone()),
-						),
-						self.log,
-					);
 					self.challenge_and_counter_round(
 						Action::Stealing(target_name.clone()),
 						target_name,
@@ -633,51 +1383,102 @@ impl Coup {
 				}
 			},
 			Action::Tax => {
-				self.history.push(History::ActionTax {
+				self.record(History::ActionTax {
 					by: context.name.clone(),
 				});
-				Self::log(
-					format_args!(
-						"🃏  {} takes tax with the \x1b[33mDuke\x1b[39m",
-						self.bots[self.playing_bots[self.turn]],
-					),
-					self.log,
-				);
 				self.challenge_round_only(Action::Tax);
 			},
-		}
-
-		// Let's filter out all dead bots
-		self.playing_bots = self
-			.playing_bots
-			.iter()
-			.filter(|bot_index| !self.bots[**bot_index].cards.is_empty())
-			.copied()
-			.collect::<Vec<usize>>();
-
-		// We move to the next turn (turn is the moving index self.playing_bots)
-		self.turn = if self.playing_bots.is_empty()
-			|| self.turn >= self.playing_bots.len() - 1
-		{
-			0
-		} else {
-			self.turn + 1
-		};
-	}
-
-	fn get_bot_list_starting_from_name(&self, bot_name: &str) -> Vec<usize> {
-		let bot_index = self
-			.playing_bots
-			.iter()
-			.position(|bot_index| self.bots[*bot_index].name.clone() == bot_name)
-			.unwrap();
-		self.playing_bots[bot_index + 1..]
-			.iter()
-			.chain(self.playing_bots[..bot_index].iter())
-			.cloned()
-			.collect()
-	}
-
+			Action::Conversion(target_name) => {
+				if !self.config.reformation.factions {
+					self.penalize_bot(
+						context.name.clone(),
+						"it tried to convert without playing with factions",
+					);
+				} else if self.target_not_found(target_name.clone()) {
+					self.penalize_bot(
+						context.name.clone(),
+						"it tried to convert an unknown bot",
+					);
+				} else {
+					self.record(History::ActionConversion {
+						by: context.name.clone(),
+						target: target_name.clone(),
+					});
+					self.action_conversion(target_name);
+				}
+			},
+			Action::Embezzlement => {
+				if !self.config.reformation.factions {
+					self.penalize_bot(
+						context.name.clone(),
+						"it tried to embezzle without playing with factions",
+					);
+				} else {
+					self.record(History::ActionEmbezzlement {
+						by: context.name.clone(),
+					});
+					self.challenge_round_only(Action::Embezzlement);
+				}
+			},
+			Action::Examine(target_name) => {
+				if !self.config.reformation.inquisitor {
+					self.penalize_bot(
+						context.name.clone(),
+						"it tried to examine without playing with the Inquisitor",
+					);
+				} else if self.target_not_found(target_name.clone()) {
+					self.penalize_bot(
+						context.name.clone(),
+						"it tried to examine an unknown bot",
+					);
+				} else if self.same_faction(&context.name, &target_name) {
+					self.penalize_bot(
+						context.name.clone(),
+						"it tried to examine a bot of its own faction",
+					);
+				} else {
+					self.record(History::ActionExamine {
+						by: context.name.clone(),
+						target: target_name.clone(),
+					});
+					self.challenge_round_only(Action::Examine(target_name));
+				}
+			},
+		}
+
+		// Let's filter out all dead bots
+		self.playing_bots = self
+			.playing_bots
+			.iter()
+			.filter(|bot_index| !self.bots[**bot_index].cards.is_empty())
+			.copied()
+			.collect::<Vec<usize>>();
+
+		// We move to the next turn (turn is the moving index self.playing_bots)
+		self.turn = if self.playing_bots.is_empty()
+			|| self.turn >= self.playing_bots.len() - 1
+		{
+			0
+		} else {
+			self.turn + 1
+		};
+
+		self.emit_state();
+	}
+
+	fn get_bot_list_starting_from_name(&self, bot_name: &str) -> Vec<usize> {
+		let bot_index = self
+			.playing_bots
+			.iter()
+			.position(|bot_index| self.bots[*bot_index].name.clone() == bot_name)
+			.unwrap();
+		self.playing_bots[bot_index + 1..]
+			.iter()
+			.chain(self.playing_bots[..bot_index].iter())
+			.cloned()
+			.collect()
+	}
+
 	fn challenge_and_counter_round(
 		&mut self,
 		action: Action,
@@ -708,7 +1509,10 @@ impl Coup {
 					| Action::ForeignAid
 					| Action::Swapping
 					| Action::Income
-					| Action::Tax => {
+					| Action::Tax
+					| Action::Conversion(_)
+					| Action::Embezzlement
+					| Action::Examine(_) => {
 						unreachable!("Challenge and counter not called on other actions")
 					},
 				};
@@ -728,24 +1532,23 @@ impl Coup {
 		if !self.get_bot_by_name(target_name.clone()).cards.is_empty() {
 			// THE COUNTER CHALLENGE ROUND
 			// Does the target want to counter this action?
-			let counter =
-				self.get_bot_by_name(target_name.clone()).interface.on_counter(
-					&action,
-					playing_bot_name.clone(),
-					&self.get_context(target_name.clone()),
-				);
+			let context = self.get_context(target_name.clone());
+			let counter = self
+				.get_bot_by_name_mut(target_name.clone())
+				.interface
+				.on_counter(&action, playing_bot_name.clone(), &context);
 
 			if counter {
 				// The bot target_name is countering the action so we now ask the
 				// table if anyone would like to challenge this counter
 				match action {
 					Action::Assassination(_) => {
-						self.history.push(History::CounterAssassination {
+						self.record(History::CounterAssassination {
 							by: target_name.clone(),
 							target: playing_bot_name.clone(),
 						})
 					},
-					Action::Stealing(_) => self.history.push(History::CounterStealing {
+					Action::Stealing(_) => self.record(History::CounterStealing {
 						by: target_name.clone(),
 						target: playing_bot_name.clone(),
 					}),
@@ -753,18 +1556,13 @@ impl Coup {
 					| Action::ForeignAid
 					| Action::Swapping
 					| Action::Income
-					| Action::Tax => {
+					| Action::Tax
+					| Action::Conversion(_)
+					| Action::Embezzlement
+					| Action::Examine(_) => {
 						unreachable!("Challenge and counter not called on other actions")
 					},
 				};
-				Self::log(
-					format_args!(
-						"🛑  {} was countered by {}",
-						self.get_bot_by_name(playing_bot_name.clone()),
-						self.get_bot_by_name(target_name.clone()),
-					),
-					self.log,
-				);
 
 				if let Some(counter_challenge) = self.challenge_round(
 					ChallengeRound::Counter,
@@ -778,7 +1576,10 @@ impl Coup {
 						| Action::ForeignAid
 						| Action::Swapping
 						| Action::Income
-						| Action::Tax => {
+						| Action::Tax
+						| Action::Conversion(_)
+						| Action::Embezzlement
+						| Action::Examine(_) => {
 							unreachable!("Challenge and counter not called on other actions")
 						},
 					};
@@ -800,7 +1601,10 @@ impl Coup {
 							| Action::ForeignAid
 							| Action::Swapping
 							| Action::Income
-							| Action::Tax => unreachable!(
+							| Action::Tax
+							| Action::Conversion(_)
+							| Action::Embezzlement
+							| Action::Examine(_) => unreachable!(
 								"Challenge and counter not called on other actions"
 							),
 						}
@@ -820,7 +1624,10 @@ impl Coup {
 					| Action::ForeignAid
 					| Action::Swapping
 					| Action::Income
-					| Action::Tax => {
+					| Action::Tax
+					| Action::Conversion(_)
+					| Action::Embezzlement
+					| Action::Examine(_) => {
 						unreachable!("Challenge and counter not called on other actions")
 					},
 				}
@@ -831,7 +1638,8 @@ impl Coup {
 	fn challenge_round_only(&mut self, action: Action) {
 		// THE CHALLENGE ROUND
 		let playing_bot_name = self.bots[self.playing_bots[self.turn]].name.clone();
-		// On Action::Swapping and Action::Tax
+		// On Action::Swapping, Action::Tax, Action::Embezzlement and
+		// Action::Examine
 		// Does anyone want to challenge this action?
 		if let Some(challenger) = self.challenge_round(
 			ChallengeRound::Action,
@@ -846,29 +1654,40 @@ impl Coup {
 			);
 			if !success {
 				// The challenge was unsuccessful
-				// Discard the card and pick up a new card from the deck
-				let discard_card = match action {
-					Action::Swapping => Card::Ambassador,
-					Action::Tax => Card::Duke,
+				// Discard the cards and pick up new cards from the deck
+				let discard_cards = match action {
+					Action::Swapping => vec![self.config.reformation.exchange_card()],
+					Action::Tax => vec![Card::Duke],
+					Action::Examine(_) => vec![Card::Inquisitor],
+					// The bot showed all its cards to prove it has no Duke
+					Action::Embezzlement => {
+						self.get_bot_by_name(playing_bot_name.clone()).cards.clone()
+					},
 					Action::Coup(_)
 					| Action::Assassination(_)
 					| Action::ForeignAid
 					| Action::Income
-					| Action::Stealing(_) => {
+					| Action::Stealing(_)
+					| Action::Conversion(_) => {
 						unreachable!("Challenge only not called on other actions")
 					},
 				};
-				self.swap_card(discard_card, playing_bot_name.clone());
+				for card in discard_cards {
+					self.swap_card(card, playing_bot_name.clone());
+				}
 
 				// The challenge was unsuccessful so let's do the thing
 				match action {
 					Action::Swapping => self.action_swapping(),
 					Action::Tax => self.action_tax(),
+					Action::Embezzlement => self.action_embezzlement(),
+					Action::Examine(target) => self.action_examine(target),
 					Action::Coup(_)
 					| Action::Assassination(_)
 					| Action::ForeignAid
 					| Action::Income
-					| Action::Stealing(_) => {
+					| Action::Stealing(_)
+					| Action::Conversion(_) => {
 						unreachable!("Challenge only not called on other actions")
 					},
 				}
@@ -878,11 +1697,14 @@ impl Coup {
 			match action {
 				Action::Swapping => self.action_swapping(),
 				Action::Tax => self.action_tax(),
+				Action::Embezzlement => self.action_embezzlement(),
+				Action::Examine(target) => self.action_examine(target),
 				Action::Coup(_)
 				| Action::Assassination(_)
 				| Action::ForeignAid
 				| Action::Income
-				| Action::Stealing(_) => {
+				| Action::Stealing(_)
+				| Action::Conversion(_) => {
 					unreachable!("Challenge only not called on other actions")
 				},
 			}
@@ -898,12 +1720,18 @@ impl Coup {
 		for bot_index in
 			self.get_bot_list_starting_from_name(&playing_bot_name).iter()
 		{
-			let bot = &self.bots[*bot_index];
+			// Bots can't block their own faction
+			if self.same_faction(&playing_bot_name, &self.bots[*bot_index].name) {
+				continue;
+			}
+
+			let context = self.get_context(self.bots[*bot_index].name.clone());
+			let bot = &mut self.bots[*bot_index];
 
 			let countering = bot.interface.on_counter(
 				&Action::ForeignAid,
 				playing_bot_name.clone(),
-				&self.get_context(playing_bot_name.clone()),
+				&context,
 			);
 
 			if countering {
@@ -913,18 +1741,10 @@ impl Coup {
 		}
 
 		if !counterer_name.is_empty() {
-			self.history.push(History::CounterForeignAid {
+			self.record(History::CounterForeignAid {
 				by: counterer_name.clone(),
 				target: playing_bot_name.clone(),
 			});
-			Self::log(
-				format_args!(
-					"🛑  {} was countered by {}",
-					self.get_bot_by_name(playing_bot_name.clone()),
-					self.get_bot_by_name(counterer_name.clone()),
-				),
-				self.log,
-			);
 
 			// The bot counterer_name is countering the action so we now ask the table
 			// if anyone would like to challenge this counter
@@ -957,9 +1777,8 @@ impl Coup {
 		by: String,
 	) -> Option<String> {
 		for bot_index in self.get_bot_list_starting_from_name(&by).iter() {
-			let bot = &self.bots[*bot_index];
-
-			let context = self.get_context(bot.name.clone());
+			let context = self.get_context(self.bots[*bot_index].name.clone());
+			let bot = &mut self.bots[*bot_index];
 
 			let challenging = match challenge_type {
 				ChallengeRound::Action => {
@@ -971,14 +1790,6 @@ impl Coup {
 			};
 
 			if challenging {
-				Self::log(
-					format_args!(
-						"❓  {} was challenged by {}",
-						self.get_bot_by_name(by),
-						bot
-					),
-					self.log,
-				);
 				return Some(bot.name.clone());
 			}
 		}
@@ -992,11 +1803,17 @@ impl Coup {
 		player: String,
 		challenger: String,
 	) -> bool {
-		self.history.push(match action {
+		self.record(match action {
 			Action::Assassination(_) => History::ChallengeAssassin {
 				by: challenger.clone(),
 				target: player.clone(),
 			},
+			Action::Swapping if self.config.reformation.inquisitor => {
+				History::ChallengeInquisitor {
+					by: challenger.clone(),
+					target: player.clone(),
+				}
+			},
 			Action::Swapping => History::ChallengeAmbassador {
 				by: challenger.clone(),
 				target: player.clone(),
@@ -1009,43 +1826,78 @@ impl Coup {
 				by: challenger.clone(),
 				target: player.clone(),
 			},
-			Action::Coup(_) | Action::Income | Action::ForeignAid => {
-				unreachable!("Can't challenge Coup, Income or ForeignAid")
+			Action::Examine(_) => History::ChallengeInquisitor {
+				by: challenger.clone(),
+				target: player.clone(),
+			},
+			Action::Embezzlement => History::ChallengeEmbezzlement {
+				by: challenger.clone(),
+				target: player.clone(),
+			},
+			Action::Coup(_)
+			| Action::Income
+			| Action::ForeignAid
+			| Action::Conversion(_) => {
+				unreachable!("Can't challenge Coup, Income, ForeignAid or Conversion")
 			},
 		});
 
-		let player = self.get_bot_by_name(player.clone());
-		let challenger = self.get_bot_by_name(challenger.clone());
+		// Embezzling is a claim not to have the Duke so the challenge is won if
+		// the bot does have it
+		if action == Action::Embezzlement {
+			let cards = self.get_bot_by_name(player.clone()).cards.clone();
+			if cards.contains(&Card::Duke) {
+				self.record(History::ChallengeSuccessful {
+					by: challenger,
+					target: player.clone(),
+				});
+				self.card_loss(player);
+				return true;
+			}
+
+			self.record(History::ChallengeUnsuccessful {
+				by: challenger.clone(),
+				target: player.clone(),
+			});
+			for card in cards {
+				self.record(History::RevealCard {
+					by: player.clone(),
+					card,
+				});
+			}
+			self.card_loss(challenger);
+			return false;
+		}
 
 		let card = match action {
 			Action::Assassination(_) => Card::Assassin,
-			Action::Swapping => Card::Ambassador,
+			Action::Swapping => self.config.reformation.exchange_card(),
 			Action::Stealing(_) => Card::Captain,
 			Action::Tax => Card::Duke,
-			Action::Coup(_) | Action::Income | Action::ForeignAid => {
-				unreachable!("Can't challenge Coup, Income or ForeignAid")
+			Action::Examine(_) => Card::Inquisitor,
+			Action::Coup(_)
+			| Action::Income
+			| Action::ForeignAid
+			| Action::Conversion(_)
+			| Action::Embezzlement => {
+				unreachable!("Can't challenge Coup, Income, ForeignAid or Conversion")
 			},
 		};
 
-		if player.cards.contains(&card) {
-			Self::log(
-				format_args!(
-					"👎  The challenge was unsuccessful because {} \x1b[33mdid have the {:?}\x1b[39m",
-					player, card
-				),
-				self.log,
-			);
-			self.card_loss(challenger.name.clone());
+		if self.get_bot_by_name(player.clone()).cards.contains(&card) {
+			self.record(History::ChallengeUnsuccessful {
+				by: challenger.clone(),
+				target: player.clone(),
+			});
+			self.record(History::RevealCard { by: player, card });
+			self.card_loss(challenger);
 			false
 		} else {
-			Self::log(
-				format_args!(
-					"👍  The challenge was successful because {} \x1b[33mdidn't have the {:?}\x1b[39m",
-					player, card
-				),
-				self.log,
-			);
-			self.card_loss(player.name.clone());
+			self.record(History::ChallengeSuccessful {
+				by: challenger,
+				target: player.clone(),
+			});
+			self.card_loss(player);
 			true
 		}
 	}
@@ -1057,7 +1909,7 @@ impl Coup {
 		counterer: String,
 		challenger: String,
 	) -> bool {
-		self.history.push(match counter {
+		self.record(match counter {
 			Counter::Assassination => History::CounterChallengeContessa {
 				by: challenger.clone(),
 				target: counterer.clone(),
@@ -1066,52 +1918,58 @@ impl Coup {
 				by: challenger.clone(),
 				target: counterer.clone(),
 			},
+			Counter::Stealing if self.config.reformation.inquisitor => {
+				History::CounterChallengeCaptainInquisitor {
+					by: challenger.clone(),
+					target: counterer.clone(),
+				}
+			},
 			Counter::Stealing => History::CounterChallengeCaptainAmbassedor {
 				by: challenger.clone(),
 				target: counterer.clone(),
 			},
 		});
 
-		let counterer = self.get_bot_by_name(counterer.clone());
-		let challenger = self.get_bot_by_name(challenger.clone());
-
 		let cards = match counter {
 			Counter::Assassination => vec![Card::Contessa],
 			Counter::ForeignAid => vec![Card::Duke],
-			Counter::Stealing => vec![Card::Captain, Card::Ambassador],
+			Counter::Stealing => {
+				vec![Card::Captain, self.config.reformation.exchange_card()]
+			},
 		};
-		let card_string = cards
-			.iter()
-			.map(|card| format!("{:?}", card))
-			.collect::<Vec<String>>()
-			.join(" or the ");
-
-		if cards.iter().any(|&card| counterer.cards.contains(&card)) {
-			Self::log(
-				format_args!(
-					"👎  The counter was unsuccessful because {} \x1b[33mdid have the {}\x1b[39m",
-					counterer, card_string
-				),
-				self.log,
-			);
-			self.card_loss(challenger.name.clone());
+		let counterer_cards = &self.get_bot_by_name(counterer.clone()).cards;
+
+		if let Some(card) =
+			cards.iter().find(|&card| counterer_cards.contains(card)).copied()
+		{
+			self.record(History::CounterChallengeUnsuccessful {
+				by: challenger.clone(),
+				target: counterer.clone(),
+			});
+			self.record(History::RevealCard {
+				by: counterer,
+				card,
+			});
+			self.card_loss(challenger);
 			false
 		} else {
-			Self::log(
-				format_args!(
-					"👍  The counter was successful because {} \x1b[33mdidn't have the {}\x1b[39m",
-					counterer, card_string
-				),
-				self.log,
-			);
-			self.card_loss(counterer.name.clone());
+			self.record(History::CounterChallengeSuccessful {
+				by: challenger,
+				target: counterer.clone(),
+			});
+			self.card_loss(counterer);
 			true
 		}
 	}
 
 	fn display_score(&mut self) {
+		if self.quiet {
+			return;
+		}
 		let fps = (self.rounds as f64 / 1000.0).max(1.0) as u64;
-		if self.round == 0 || self.round % fps == 0 || self.round + 1 == self.rounds
+		if self.round == 0
+			|| self.round.is_multiple_of(fps)
+			|| self.round + 1 == self.rounds
 		{
 			if self.round > 0 {
 				print!("\x1b[{}A\x1b[2K", self.score.len() + 1);
@@ -1151,52 +2009,57 @@ impl Coup {
 
 	/// Play n number of rounds and tally up the score in the CLI.
 	pub fn looping(&mut self, rounds: u64) {
-		self.setup();
 		self.log = false;
 		self.rounds = rounds;
 
-		// Logo
-		let output = render(Options {
-			text: String::from("Coup"),
-			colors: vec![Colors::White, Colors::Yellow],
-			spaceless: true,
-			..Options::default()
-		});
-		println!("\n\n{}\x1b[4Dv{}\n\n", output.text, env!("CARGO_PKG_VERSION"));
+		if !self.quiet {
+			// Logo
+			let output = render(Options {
+				text: String::from("Coup"),
+				colors: vec![Colors::White, Colors::Yellow],
+				spaceless: true,
+				..Options::default()
+			});
+			println!("\n\n{}\x1b[4Dv{}\n\n", output.text, env!("CARGO_PKG_VERSION"));
 
-		println!(
-			" Starting \x1b[36m{}\x1b[39m rounds",
-			Self::format_number_with_separator(rounds)
-		);
+			println!(
+				" Starting \x1b[36m{}\x1b[39m rounds with seed \x1b[36m{}\x1b[39m",
+				Self::format_number_with_separator(rounds),
+				self.seed
+			);
+
+			println!(" ╔═════════════════╗\n ║ 🎲🎲 \x1b[1mBOARD\x1b[0m 🎲🎲 ║\n ╚═════════════════╝\x1b[?25l");
+		}
 
-		println!(" ╔═════════════════╗\n ║ 🎲🎲 \x1b[1mBOARD\x1b[0m 🎲🎲 ║\n ╚═════════════════╝\x1b[?25l");
 		self.display_score();
 		for round in 0..rounds {
-			self.setup();
 			self.play();
 			// TODO: detect "stop" and record log in debug mode
 			self.round = round + 1;
 			self.display_score();
 		}
 
-		println!(
-			"\x1b[?25h\n 🎉🎉🎉 The winner is: \x1b[1m{}\x1b[0m\n",
-			self
-				.score
-				.iter()
-				.max_by(|(_, a), (_, b)| a
-					.partial_cmp(b)
-					.unwrap_or(std::cmp::Ordering::Equal))
-				.unwrap()
-				.0
-		);
+		if !self.quiet {
+			println!(
+				"\x1b[?25h\n 🎉🎉🎉 The winner is: \x1b[1m{}\x1b[0m\n",
+				self
+					.score
+					.iter()
+					.max_by(|(_, a), (_, b)| a
+						.partial_cmp(b)
+						.unwrap_or(std::cmp::Ordering::Equal))
+					.unwrap()
+					.0
+			);
+		}
 	}
 
 	// *******************************| Actions |****************************** //
 	fn action_assassination(&mut self, target: String) {
 		let playing_bot_coins = self.bots[self.playing_bots[self.turn]].coins;
 		let playing_bot_name = self.bots[self.playing_bots[self.turn]].name.clone();
-		if playing_bot_coins < 3 {
+		let cost = self.config.assassination_cost;
+		if playing_bot_coins < cost {
 			self.penalize_bot(
 				playing_bot_name.clone(),
 				"it tried to assassinate someone with insufficient funds",
@@ -1207,8 +2070,9 @@ impl Coup {
 				"it tried to assassinate an unknown bot",
 			);
 		} else {
-			// Paying the fee
-			self.bots[self.playing_bots[self.turn]].coins = playing_bot_coins - 3;
+			// Paying the fee into the treasury
+			self.bots[self.playing_bots[self.turn]].coins = playing_bot_coins - cost;
+			self.treasury += cost;
 
 			// Taking a card from the target bot
 			self.card_loss(target);
@@ -1218,7 +2082,8 @@ impl Coup {
 	fn action_couping(&mut self, target: String) {
 		let playing_bot_coins = self.bots[self.playing_bots[self.turn]].coins;
 		let playing_bot_name = self.bots[self.playing_bots[self.turn]].name.clone();
-		if playing_bot_coins < 7 {
+		let cost = self.config.coup_cost;
+		if playing_bot_coins < cost {
 			self.penalize_bot(
 				playing_bot_name.clone(),
 				"it tried to coup someone with insufficient funds",
@@ -1229,8 +2094,9 @@ impl Coup {
 				"it tried to coup an unknown bot",
 			);
 		} else {
-			// Paying the fee
-			self.bots[self.playing_bots[self.turn]].coins = playing_bot_coins - 7;
+			// Paying the fee into the treasury
+			self.bots[self.playing_bots[self.turn]].coins = playing_bot_coins - cost;
+			self.treasury += cost;
 
 			// Taking a card from the target bot
 			self.card_loss(target);
@@ -1239,20 +2105,26 @@ impl Coup {
 
 	fn action_foraign_aid(&mut self) {
 		let coins = self.bots[self.playing_bots[self.turn]].coins;
-		self.bots[self.playing_bots[self.turn]].coins = coins + 2;
+		self.bots[self.playing_bots[self.turn]].coins =
+			coins + self.take_from_treasury(2);
 	}
 
 	fn action_swapping(&mut self) {
+		if self.config.reformation.inquisitor {
+			self.action_exchange();
+			return;
+		}
+
 		let mut all_available_cards =
 			self.bots[self.playing_bots[self.turn]].cards.clone();
 		let card1 = self.deck.pop().unwrap();
 		let card2 = self.deck.pop().unwrap();
 		let cards_from_deck = [card1, card2];
-		let swapped_cards =
-			self.bots[self.playing_bots[self.turn]].interface.on_swapping_cards(
-				cards_from_deck,
-				&self.get_context(self.bots[self.playing_bots[self.turn]].name.clone()),
-			);
+		let context =
+			self.get_context(self.bots[self.playing_bots[self.turn]].name.clone());
+		let swapped_cards = self.bots[self.playing_bots[self.turn]]
+			.interface
+			.on_swapping_cards(cards_from_deck, &context);
 		all_available_cards.push(card1);
 		all_available_cards.push(card2);
 
@@ -1266,7 +2138,7 @@ impl Coup {
 		} else {
 			self.deck.push(swapped_cards[0]);
 			self.deck.push(swapped_cards[1]);
-			self.deck.shuffle(&mut thread_rng());
+			self.deck.shuffle(&mut self.rng);
 
 			// removing the discarded cards from the pool and giving it to the bot
 			if let Some(index) =
@@ -1280,18 +2152,22 @@ impl Coup {
 				all_available_cards.remove(index);
 			}
 			self.bots[self.playing_bots[self.turn]].cards = all_available_cards;
+			self.record(History::ExchangeCards {
+				by: self.bots[self.playing_bots[self.turn]].name.clone(),
+			});
 		}
 	}
 
 	fn action_income(&mut self) {
 		let playing_bot_coins = self.bots[self.playing_bots[self.turn]].coins;
-		self.bots[self.playing_bots[self.turn]].coins = playing_bot_coins + 1;
+		self.bots[self.playing_bots[self.turn]].coins =
+			playing_bot_coins + self.take_from_treasury(1);
 	}
 
 	fn action_stealing(&mut self, target: String) {
 		let coins = self.bots[self.playing_bots[self.turn]].coins;
 		let target_coins = self.get_bot_by_name(target.clone()).coins;
-		let booty = std::cmp::min(target_coins, 2);
+		let booty = std::cmp::min(target_coins, self.config.stealing_amount);
 		self.bots[self.playing_bots[self.turn]].coins = coins + booty;
 		self
 			.bots
@@ -1303,7 +2179,102 @@ impl Coup {
 
 	fn action_tax(&mut self) {
 		let coins = self.bots[self.playing_bots[self.turn]].coins;
-		self.bots[self.playing_bots[self.turn]].coins = coins + 3;
+		self.bots[self.playing_bots[self.turn]].coins =
+			coins + self.take_from_treasury(self.config.tax_amount);
+	}
+
+	fn action_conversion(&mut self, target: String) {
+		let playing_bot_coins = self.bots[self.playing_bots[self.turn]].coins;
+		let playing_bot_name = self.bots[self.playing_bots[self.turn]].name.clone();
+		// Converting yourself is cheaper than converting someone else
+		let cost = if target == playing_bot_name { 1 } else { 2 };
+		if playing_bot_coins < cost {
+			self.penalize_bot(
+				playing_bot_name.clone(),
+				"it tried to convert someone with insufficient funds",
+			);
+		} else {
+			// Paying the Treasury Reserve
+			self.bots[self.playing_bots[self.turn]].coins = playing_bot_coins - cost;
+			self.treasury_reserve += cost;
+
+			let bot = self.get_bot_by_name_mut(target);
+			bot.allegiance = bot.allegiance.map(Allegiance::other);
+		}
+	}
+
+	fn action_embezzlement(&mut self) {
+		let coins = self.bots[self.playing_bots[self.turn]].coins;
+		self.bots[self.playing_bots[self.turn]].coins =
+			coins + self.treasury_reserve;
+		self.treasury_reserve = 0;
+	}
+
+	// Swapping with the Inquisitor only draws a single card
+	fn action_exchange(&mut self) {
+		let playing_bot_name = self.bots[self.playing_bots[self.turn]].name.clone();
+		let mut all_available_cards =
+			self.bots[self.playing_bots[self.turn]].cards.clone();
+		let new_card = self.deck.pop().unwrap();
+		let context = self.get_context(playing_bot_name.clone());
+		let returned_card = self.bots[self.playing_bots[self.turn]]
+			.interface
+			.on_exchanging_card(new_card, &context);
+		all_available_cards.push(new_card);
+
+		if let Some(index) =
+			all_available_cards.iter().position(|&c| c == returned_card)
+		{
+			all_available_cards.remove(index);
+			self.deck.push(returned_card);
+			self.deck.shuffle(&mut self.rng);
+			self.bots[self.playing_bots[self.turn]].cards = all_available_cards;
+			self.record(History::ExchangeCards {
+				by: playing_bot_name,
+			});
+		} else {
+			// The card goes back so the deck stays complete
+			self.deck.push(new_card);
+			self.deck.shuffle(&mut self.rng);
+			self.penalize_bot(
+				playing_bot_name,
+				"it tried to exchange a card it didn't have",
+			);
+		}
+	}
+
+	fn action_examine(&mut self, target: String) {
+		let playing_bot_name = self.bots[self.playing_bots[self.turn]].name.clone();
+		// The target may have lost its last card challenging the Inquisitor
+		if self.get_bot_by_name(target.clone()).cards.is_empty() {
+			return;
+		}
+
+		let context = self.get_context(target.clone());
+		let shown_card = self
+			.get_bot_by_name_mut(target.clone())
+			.interface
+			.on_examine(playing_bot_name.clone(), &context);
+		if !self.get_bot_by_name(target.clone()).cards.contains(&shown_card) {
+			self.penalize_bot(target, "it showed a card it didn't have");
+			return;
+		}
+		self.record(History::ExamineCard {
+			by: playing_bot_name.clone(),
+			target: target.clone(),
+		});
+
+		let context = self.get_context(playing_bot_name.clone());
+		let exchange = self.bots[self.playing_bots[self.turn]]
+			.interface
+			.on_examined_card(shown_card, target.clone(), &context);
+		if exchange {
+			self.record(History::ForceExchange {
+				by: playing_bot_name,
+				target: target.clone(),
+			});
+			self.swap_card(shown_card, target);
+		}
 	}
 }
 
@@ -1344,7 +2315,7 @@ impl fmt::Debug for Coup {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::bots::StaticBot;
+	use crate::bots::{HonestBot, HumanBot, RandomBot, StaticBot};
 
 	#[test]
 	fn test_new() {
@@ -1381,6 +2352,19 @@ mod tests {
 		assert_eq!(deck.iter().filter(|&card| card == &Card::Duke).count(), 3);
 	}
 
+	#[test]
+	fn test_new_table_deck() {
+		let deck = Coup::new_table_deck_with_rng(
+			&Reformation::default(),
+			7,
+			&mut thread_rng(),
+		);
+		assert_eq!(deck.len(), 25);
+		assert_eq!(deck.iter().filter(|&card| card == &Card::Duke).count(), 5);
+		assert_eq!(Coup::copies_per_card(6), 3);
+		assert_eq!(Coup::copies_per_card(10), 5);
+	}
+
 	#[test]
 	fn test_setup() {
 		let mut coup = Coup::new(vec![
@@ -1414,114 +2398,634 @@ mod tests {
 		assert_eq!(coup.moves, 0);
 	}
 
-	// TODO: test_log
+	#[test]
+	fn test_setup_big_table() {
+		let mut coup =
+			Coup::new((0..12).map(|_| Box::new(StaticBot) as _).collect());
+		coup.set_table_size(10);
+		coup.setup();
+
+		assert_eq!(coup.playing_bots.len(), 10);
+		assert!(coup
+			.playing_bots
+			.iter()
+			.all(|bot| coup.bots[*bot].cards.len() == 2));
+		assert_eq!(coup.deck.len(), 5);
+		assert_eq!(coup.get_context(String::from("StaticBot")).copies, 5);
+
+		coup.set_table_size(20);
+		coup.setup();
+
+		assert_eq!(coup.playing_bots.len(), 10);
+	}
 
 	#[test]
-	fn test_get_bot_by_name() {
+	fn test_set_config() {
 		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
-		coup.setup();
+		coup.set_config(GameConfig {
+			table_size: 42,
+			..GameConfig::default()
+		});
+
+		assert_eq!(coup.get_config().table_size, 10);
+
+		coup.set_reformation(Reformation::all());
+		coup.set_table_size(1);
 
 		assert_eq!(
-			coup.get_bot_by_name(String::from("StaticBot")).name,
-			String::from("StaticBot")
-		);
-		assert_eq!(
-			coup.get_bot_by_name(String::from("StaticBot 2")).name,
-			String::from("StaticBot 2")
+			coup.get_config(),
+			&GameConfig {
+				table_size: 2,
+				reformation: Reformation::all(),
+				..GameConfig::default()
+			}
 		);
 	}
 
 	#[test]
-	fn test_get_other_bots() {
+	fn test_setup_config() {
 		let mut coup = Coup::new(vec![
 			Box::new(StaticBot),
 			Box::new(StaticBot),
 			Box::new(StaticBot),
-			Box::new(StaticBot),
-			Box::new(StaticBot),
 		]);
+		coup.set_config(GameConfig {
+			starting_coins: 4,
+			first_player_coins: Some(1),
+			..GameConfig::default()
+		});
 		coup.setup();
 
-		coup.playing_bots = vec![0, 1, 2, 3, 4];
-		coup.turn = 0;
-		assert!(coup.get_other_bots().contains(&OtherBot {
-			name: String::from("StaticBot"),
-			coins: 2,
-			cards: 2
-		}));
-		assert!(coup.get_other_bots().contains(&OtherBot {
-			name: String::from("StaticBot 2"),
-			coins: 2,
-			cards: 2
-		}));
-		assert!(coup.get_other_bots().contains(&OtherBot {
-			name: String::from("StaticBot 3"),
-			coins: 2,
-			cards: 2
-		}));
-		assert!(coup.get_other_bots().contains(&OtherBot {
-			name: String::from("StaticBot 4"),
-			coins: 2,
-			cards: 2
-		}));
-		assert!(coup.get_other_bots().contains(&OtherBot {
-			name: String::from("StaticBot 5"),
-			coins: 2,
-			cards: 2
-		}));
+		assert_eq!(coup.bots[coup.playing_bots[0]].coins, 1);
+		assert_eq!(coup.bots[coup.playing_bots[1]].coins, 4);
+		assert_eq!(coup.bots[coup.playing_bots[2]].coins, 4);
+		assert_eq!(
+			coup.get_context(String::from("StaticBot")).config,
+			*coup.get_config()
+		);
+	}
 
-		coup.playing_bots = vec![4, 3, 2, 1, 0];
-		assert!(coup.get_other_bots().contains(&OtherBot {
-			name: String::from("StaticBot"),
-			coins: 2,
-			cards: 2
-		}));
-		assert!(coup.get_other_bots().contains(&OtherBot {
-			name: String::from("StaticBot 2"),
-			coins: 2,
-			cards: 2
-		}));
-		assert!(coup.get_other_bots().contains(&OtherBot {
-			name: String::from("StaticBot 3"),
-			coins: 2,
-			cards: 2
-		}));
-		assert!(coup.get_other_bots().contains(&OtherBot {
-			name: String::from("StaticBot 4"),
+	#[test]
+	fn test_setup_two_player() {
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+		coup.set_config(GameConfig::two_player());
+		coup.setup();
+
+		assert_eq!(coup.bots[coup.playing_bots[0]].coins, 1);
+		assert_eq!(coup.bots[coup.playing_bots[1]].coins, 2);
+		assert_eq!(coup.bots[0].cards.len(), 2);
+		assert_eq!(coup.bots[1].cards.len(), 2);
+		assert_eq!(coup.deck.len(), 11);
+
+		let mut all_cards = coup.deck.clone();
+		all_cards.extend(coup.bots[0].cards.clone());
+		all_cards.extend(coup.bots[1].cards.clone());
+		assert_eq!(all_cards.len(), 15);
+		assert_eq!(
+			all_cards.iter().filter(|&card| card == &Card::Ambassador).count(),
+			3
+		);
+		assert_eq!(
+			all_cards.iter().filter(|&card| card == &Card::Assassin).count(),
+			3
+		);
+		assert_eq!(
+			all_cards.iter().filter(|&card| card == &Card::Captain).count(),
+			3
+		);
+		assert_eq!(
+			all_cards.iter().filter(|&card| card == &Card::Contessa).count(),
+			3
+		);
+		assert_eq!(all_cards.iter().filter(|&card| card == &Card::Duke).count(), 3);
+	}
+
+	#[test]
+	fn test_pick_starting_cards() {
+		struct TestBot;
+		impl BotInterface for TestBot {
+			fn get_name(&self) -> String {
+				String::from("TestBot")
+			}
+			fn on_starting_cards(
+				&mut self,
+				dealt_cards: Vec<Card>,
+				context: &Context,
+			) -> [Card; 2] {
+				assert_eq!(context.cards, vec![]);
+				[dealt_cards[4], dealt_cards[2]]
+			}
+		}
+
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(TestBot)]);
+		coup.setup();
+		coup.deck = vec![];
+
+		coup.pick_starting_cards(
+			1,
+			vec![
+				Card::Duke,
+				Card::Captain,
+				Card::Assassin,
+				Card::Contessa,
+				Card::Ambassador,
+			],
+		);
+
+		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador, Card::Assassin]);
+		assert_eq!(coup.deck, vec![Card::Duke, Card::Captain, Card::Contessa]);
+	}
+
+	#[test]
+	fn test_pick_starting_cards_faulty_bot() {
+		struct TestBot;
+		impl BotInterface for TestBot {
+			fn get_name(&self) -> String {
+				String::from("TestBot")
+			}
+			fn on_starting_cards(
+				&mut self,
+				_dealt_cards: Vec<Card>,
+				_context: &Context,
+			) -> [Card; 2] {
+				[Card::Duke, Card::Duke]
+			}
+		}
+
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(TestBot)]);
+		coup.setup();
+		coup.deck = vec![];
+
+		coup.pick_starting_cards(
+			1,
+			vec![
+				Card::Captain,
+				Card::Duke,
+				Card::Assassin,
+				Card::Contessa,
+				Card::Ambassador,
+			],
+		);
+
+		assert_eq!(coup.bots[1].cards, vec![Card::Captain, Card::Duke]);
+		assert_eq!(
+			coup.deck,
+			vec![Card::Assassin, Card::Contessa, Card::Ambassador]
+		);
+	}
+
+	#[test]
+	fn test_setup_with_seed() {
+		let mut coup1 = Coup::new_with_seed(
+			vec![
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+			],
+			42,
+		);
+		let mut coup2 = Coup::new_with_seed(
+			vec![
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+				Box::new(StaticBot),
+			],
+			42,
+		);
+		coup1.setup();
+		coup2.setup();
+
+		assert_eq!(coup1.get_seed(), 42);
+		assert_eq!(coup1.get_game_seeds(), coup2.get_game_seeds());
+		assert_eq!(coup1.playing_bots, coup2.playing_bots);
+		assert_eq!(coup1.deck, coup2.deck);
+		for (bot1, bot2) in coup1.bots.iter().zip(coup2.bots.iter()) {
+			assert_eq!(bot1.cards, bot2.cards);
+		}
+
+		coup1.setup();
+		assert_eq!(coup1.get_game_seeds().len(), 2);
+		assert_ne!(coup1.get_game_seeds()[0], coup1.get_game_seeds()[1]);
+	}
+
+	#[test]
+	fn test_replay() {
+		let mut coup = Coup::new_with_seed(
+			vec![
+				Box::new(HonestBot),
+				Box::new(HonestBot),
+				Box::new(StaticBot),
+				Box::new(HonestBot),
+			],
+			1337,
+		);
+		coup.log = false;
+		coup.play();
+		let game_seed = coup.get_game_seeds()[0];
+		let history = coup.history.clone();
+		let discard_pile = coup.discard_pile.clone();
+
+		let mut coup = Coup::new_with_seed(
+			vec![
+				Box::new(HonestBot),
+				Box::new(HonestBot),
+				Box::new(StaticBot),
+				Box::new(HonestBot),
+			],
+			7,
+		);
+		coup.log = false;
+		coup.replay(game_seed);
+
+		assert_eq!(coup.get_game_seeds(), vec![game_seed]);
+		assert_eq!(coup.history, history);
+		assert_eq!(coup.discard_pile, discard_pile);
+	}
+
+	// TODO: test_log
+
+	#[test]
+	fn test_log_sinks() {
+		let sink = game_log::MemorySink::new();
+		let mut coup = Coup::new_with_seed(
+			vec![
+				Box::new(HonestBot),
+				Box::new(HonestBot),
+				Box::new(StaticBot),
+			],
+			3,
+		);
+		coup.log = false;
+		coup.add_log_sink(Box::new(sink.clone()));
+		coup.play();
+
+		let events = sink.events();
+		assert!(matches!(
+			events.first(),
+			Some(LogEvent::Setup { round: 0, seed, seats }) if *seed == coup.get_game_seeds()[0] && seats.len() == 3
+		));
+		assert!(matches!(
+			events.last(),
+			Some(LogEvent::Result { moves, .. }) if *moves == coup.moves
+		));
+		assert_eq!(
+			events
+				.iter()
+				.filter_map(|event| match event {
+					LogEvent::Event { event } => Some(event.clone()),
+					_ => None,
+				})
+				.collect::<Vec<History>>(),
+			coup.history
+		);
+		assert_eq!(
+			events
+				.iter()
+				.filter(|event| matches!(event, LogEvent::State { .. }))
+				.count(),
+			coup.moves
+		);
+	}
+
+	#[test]
+	fn test_human_bot() {
+		let input = "oops\n0\ntax\n4\n5\n1\n";
+		let mut coup = Coup::new_with_seed(
+			vec![
+				Box::new(HumanBot::with_io("You", input.as_bytes(), std::io::sink())),
+				Box::new(StaticBot),
+			],
+			5,
+		);
+		coup.log = false;
+		coup.play();
+
+		assert!(!coup
+			.history
+			.iter()
+			.any(|event| matches!(event, History::Penalty { .. })));
+		assert!(coup.history.contains(&History::ActionTax {
+			by: String::from("You")
+		}));
+		assert!(coup.history.contains(&History::ActionStealing {
+			by: String::from("You"),
+			target: String::from("StaticBot")
+		}));
+	}
+
+	#[test]
+	fn test_human_bot_conversion() {
+		// With one coin only converting yourself is offered
+		let input = "conversion\nStaticBot\n1\n";
+		let mut bot = HumanBot::with_io("You", input.as_bytes(), std::io::sink());
+		let bot_info = |name: &str, allegiance| OtherBot {
+			name: String::from(name),
+			coins: 1,
+			cards: 2,
+			allegiance: Some(allegiance),
+		};
+		let context = Context {
+			name: String::from("You"),
+			cards: vec![Card::Duke, Card::Captain],
+			coins: 1,
+			playing_bots: vec![
+				bot_info("You", Allegiance::Loyalist),
+				bot_info("StaticBot", Allegiance::Reformist),
+			],
+			discard_pile: vec![],
+			history: vec![],
+			moves: 0,
+			score: vec![],
+			config: GameConfig {
+				reformation: Reformation::all(),
+				..GameConfig::default()
+			},
+			treasury: 48,
+			treasury_reserve: 0,
+			copies: 3,
+		};
+
+		assert_eq!(bot.on_turn(&context), Action::Conversion(String::from("You")));
+	}
+
+	#[test]
+	fn test_new_from_registry() {
+		let instances = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+		let counter = instances.clone();
+		let mut registry = BotRegistry::default();
+		registry.register(
+			bots::BotEntry::new(
+				"Counted",
+				"Counts its instances",
+				bot::factory(move || {
+					counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+					StaticBot
+				}),
+			)
+			.fresh_per_game(),
+		);
+
+		assert!(Coup::new_from_registry(&registry, &["Kate"], 1).is_err());
+
+		let mut coup =
+			Coup::new_from_registry(&registry, &["counted", "HonestBot"], 1).unwrap();
+		coup.set_quiet(true);
+		assert_eq!(instances.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+		coup.looping(3);
+		assert_eq!(instances.load(std::sync::atomic::Ordering::SeqCst), 3);
+		assert_eq!(coup.bots[0].name, "StaticBot");
+	}
+
+	struct HookBot {
+		calls: std::rc::Rc<std::cell::RefCell<Vec<String>>>,
+	}
+
+	impl BotInterface for HookBot {
+		fn get_name(&self) -> String {
+			String::from("HookBot")
+		}
+
+		fn on_game_start(&mut self, context: &Context) {
+			self.calls.borrow_mut().push(format!("start {}", context.cards.len()));
+		}
+
+		fn on_game_end(
+			&mut self,
+			winners: &[String],
+			hands: &[Seat],
+			_context: &Context,
+		) {
+			self.calls.borrow_mut().push(format!(
+				"end {} {}",
+				winners.join(","),
+				hands.len()
+			));
+		}
+
+		fn on_event(&mut self, _event: &History) {
+			self.calls.borrow_mut().push(String::from("event"));
+		}
+	}
+
+	#[test]
+	fn test_lifecycle_hooks() {
+		let calls = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+		let mut coup = Coup::new_with_seed(
+			vec![
+				Box::new(HookBot {
+					calls: calls.clone(),
+				}),
+				Box::new(HonestBot),
+			],
+			9,
+		);
+		coup.set_quiet(true);
+		coup.play();
+
+		let winners = coup
+			.playing_bots
+			.iter()
+			.map(|bot_index| coup.bots[*bot_index].name.clone())
+			.collect::<Vec<String>>();
+		let calls = calls.borrow();
+		assert_eq!(calls.first().unwrap(), "start 2");
+		assert_eq!(calls.last().unwrap(), &format!("end {} 2", winners.join(",")));
+		assert_eq!(
+			calls.iter().filter(|call| *call == "event").count(),
+			coup.history.len()
+		);
+	}
+
+	struct TurnCounterBot {
+		turns: usize,
+		report: std::rc::Rc<std::cell::RefCell<Vec<(String, usize)>>>,
+	}
+
+	impl BotInterface for TurnCounterBot {
+		fn get_name(&self) -> String {
+			String::from("TurnCounterBot")
+		}
+
+		fn on_turn(&mut self, _context: &Context) -> Action {
+			self.turns += 1;
+			Action::Income
+		}
+
+		fn on_game_end(
+			&mut self,
+			_winners: &[String],
+			_hands: &[Seat],
+			context: &Context,
+		) {
+			self.report.borrow_mut().push((context.name.clone(), self.turns));
+		}
+	}
+
+	#[test]
+	fn test_stateful_bots() {
+		let report = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+		let mut coup = Coup::new_with_seed(
+			vec![
+				Box::new(TurnCounterBot {
+					turns: 0,
+					report: report.clone(),
+				}),
+				Box::new(TurnCounterBot {
+					turns: 0,
+					report: report.clone(),
+				}),
+			],
+			4,
+		);
+		coup.set_quiet(true);
+		coup.play();
+
+		// Each instance keeps its own count
+		for (name, turns) in report.borrow().iter() {
+			assert_eq!(
+				*turns,
+				coup
+					.history
+					.iter()
+					.filter(|event| {
+						matches!(event, History::ActionIncome { by } if by == name)
+					})
+					.count()
+			);
+		}
+		assert_eq!(report.borrow().len(), 2);
+	}
+
+	#[test]
+	fn test_get_bot_by_name() {
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+		coup.setup();
+
+		assert_eq!(
+			coup.get_bot_by_name(String::from("StaticBot")).name,
+			String::from("StaticBot")
+		);
+		assert_eq!(
+			coup.get_bot_by_name(String::from("StaticBot 2")).name,
+			String::from("StaticBot 2")
+		);
+	}
+
+	#[test]
+	fn test_get_other_bots() {
+		let mut coup = Coup::new(vec![
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+		]);
+		coup.setup();
+
+		coup.playing_bots = vec![0, 1, 2, 3, 4];
+		coup.turn = 0;
+		assert!(coup.get_other_bots().contains(&OtherBot {
+			name: String::from("StaticBot"),
+			coins: 2,
+			cards: 2,
+			allegiance: None
+		}));
+		assert!(coup.get_other_bots().contains(&OtherBot {
+			name: String::from("StaticBot 2"),
+			coins: 2,
+			cards: 2,
+			allegiance: None
+		}));
+		assert!(coup.get_other_bots().contains(&OtherBot {
+			name: String::from("StaticBot 3"),
+			coins: 2,
+			cards: 2,
+			allegiance: None
+		}));
+		assert!(coup.get_other_bots().contains(&OtherBot {
+			name: String::from("StaticBot 4"),
+			coins: 2,
+			cards: 2,
+			allegiance: None
+		}));
+		assert!(coup.get_other_bots().contains(&OtherBot {
+			name: String::from("StaticBot 5"),
+			coins: 2,
+			cards: 2,
+			allegiance: None
+		}));
+
+		coup.playing_bots = vec![4, 3, 2, 1, 0];
+		assert!(coup.get_other_bots().contains(&OtherBot {
+			name: String::from("StaticBot"),
+			coins: 2,
+			cards: 2,
+			allegiance: None
+		}));
+		assert!(coup.get_other_bots().contains(&OtherBot {
+			name: String::from("StaticBot 2"),
+			coins: 2,
+			cards: 2,
+			allegiance: None
+		}));
+		assert!(coup.get_other_bots().contains(&OtherBot {
+			name: String::from("StaticBot 3"),
+			coins: 2,
+			cards: 2,
+			allegiance: None
+		}));
+		assert!(coup.get_other_bots().contains(&OtherBot {
+			name: String::from("StaticBot 4"),
 			coins: 2,
-			cards: 2
+			cards: 2,
+			allegiance: None
 		}));
 		assert!(coup.get_other_bots().contains(&OtherBot {
 			name: String::from("StaticBot 5"),
 			coins: 2,
-			cards: 2
+			cards: 2,
+			allegiance: None
 		}));
 
 		coup.turn = 2;
 		assert!(coup.get_other_bots().contains(&OtherBot {
 			name: String::from("StaticBot"),
 			coins: 2,
-			cards: 2
+			cards: 2,
+			allegiance: None
 		}));
 		assert!(coup.get_other_bots().contains(&OtherBot {
 			name: String::from("StaticBot 2"),
 			coins: 2,
-			cards: 2
+			cards: 2,
+			allegiance: None
 		}));
 		assert!(coup.get_other_bots().contains(&OtherBot {
 			name: String::from("StaticBot 3"),
 			coins: 2,
-			cards: 2
+			cards: 2,
+			allegiance: None
 		}));
 		assert!(coup.get_other_bots().contains(&OtherBot {
 			name: String::from("StaticBot 4"),
 			coins: 2,
-			cards: 2
+			cards: 2,
+			allegiance: None
 		}));
 		assert!(coup.get_other_bots().contains(&OtherBot {
 			name: String::from("StaticBot 5"),
 			coins: 2,
-			cards: 2
+			cards: 2,
+			allegiance: None
 		}));
 
 		coup.bots[0].cards = vec![];
@@ -1533,11 +3037,13 @@ mod tests {
 			name: String::from("StaticBot 2"),
 			coins: 2,
 			cards: 1,
+			allegiance: None
 		}));
 		assert!(coup.get_other_bots().contains(&OtherBot {
 			name: String::from("StaticBot 3"),
 			coins: 2,
 			cards: 2,
+			allegiance: None
 		}));
 	}
 
@@ -1560,20 +3066,27 @@ mod tests {
 					OtherBot {
 						name: String::from("StaticBot"),
 						coins: 2,
-						cards: 2
+						cards: 2,
+						allegiance: None
 					},
 					OtherBot {
 						name: String::from("StaticBot 2"),
 						coins: 2,
-						cards: 2
+						cards: 2,
+						allegiance: None
 					}
 				],
 				discard_pile: vec![],
 				history: vec![],
+				moves: 0,
 				score: vec![
 					(String::from("StaticBot"), 0.0),
 					(String::from("StaticBot 2"), 0.0)
 				],
+				config: GameConfig::default(),
+				treasury: 46,
+				treasury_reserve: 0,
+				copies: 3,
 			}
 		);
 
@@ -1588,20 +3101,27 @@ mod tests {
 					OtherBot {
 						name: String::from("StaticBot"),
 						coins: 2,
-						cards: 2
+						cards: 2,
+						allegiance: None
 					},
 					OtherBot {
 						name: String::from("StaticBot 2"),
 						coins: 2,
-						cards: 2
+						cards: 2,
+						allegiance: None
 					}
 				],
 				discard_pile: vec![],
 				history: vec![],
+				moves: 0,
 				score: vec![
 					(String::from("StaticBot"), 0.0),
 					(String::from("StaticBot 2"), 0.0)
 				],
+				config: GameConfig::default(),
+				treasury: 46,
+				treasury_reserve: 0,
+				copies: 3,
 			}
 		);
 	}
@@ -1628,7 +3148,7 @@ mod tests {
 			fn get_name(&self) -> String {
 				String::from("TestBot")
 			}
-			fn on_card_loss(&self, _context: &Context) -> Card {
+			fn on_card_loss(&mut self, _context: &Context) -> Card {
 				Card::Duke
 			}
 		}
@@ -1644,6 +3164,23 @@ mod tests {
 		assert_eq!(coup.bots[0].cards, vec![Card::Ambassador, Card::Duke]);
 		assert_eq!(coup.bots[1].cards, vec![]);
 		assert_eq!(coup.discard_pile, vec![Card::Captain, Card::Assassin]);
+		assert_eq!(
+			coup.history,
+			vec![
+				History::Penalty {
+					by: String::from("TestBot"),
+					reason: String::from("it discarded a card(Duke) it didn't have"),
+				},
+				History::LoseInfluence {
+					by: String::from("TestBot"),
+					card: Card::Captain,
+				},
+				History::LoseInfluence {
+					by: String::from("TestBot"),
+					card: Card::Assassin,
+				},
+			]
+		);
 	}
 
 	// TODO: test_penalize_bot
@@ -1653,9 +3190,9 @@ mod tests {
 		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
 		coup.setup();
 
-		assert_eq!(coup.target_not_found(String::from("StaticBot")), false);
-		assert_eq!(coup.target_not_found(String::from("StaticBot 3")), true);
-		assert_eq!(coup.target_not_found(String::from("StaticBot 2")), false);
+		assert!(!coup.target_not_found(String::from("StaticBot")));
+		assert!(coup.target_not_found(String::from("StaticBot 3")));
+		assert!(!coup.target_not_found(String::from("StaticBot 2")));
 
 		let mut coup = Coup::new(vec![
 			Box::new(StaticBot),
@@ -1669,11 +3206,11 @@ mod tests {
 		coup.setup();
 		coup.playing_bots = vec![0, 1, 2, 3, 4, 5];
 
-		assert_eq!(coup.target_not_found(String::from("StaticBot 7")), true);
+		assert!(coup.target_not_found(String::from("StaticBot 7")));
 
 		coup.playing_bots = vec![1, 2, 3, 4, 5, 6];
 
-		assert_eq!(coup.target_not_found(String::from("StaticBot 7")), false);
+		assert!(!coup.target_not_found(String::from("StaticBot 7")));
 	}
 
 	#[test]
@@ -1738,17 +3275,39 @@ mod tests {
 				(String::from("StaticBot 5"), -0.3333333333333333),
 			]
 		);
-	}
 
-	#[test]
-	fn test_swap_card() {
-		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+		// Eight bots at a table of six, one winner
+		coup = Coup::new((0..8).map(|_| Box::new(StaticBot) as _).collect());
 		coup.setup();
-		coup.playing_bots = vec![0, 1];
+		coup.seats = vec![0, 1, 2, 3, 4, 5];
 
-		coup.bots[0].cards = vec![Card::Ambassador, Card::Ambassador];
-		coup.bots[1].cards = vec![Card::Assassin, Card::Captain];
-		coup.deck = vec![Card::Ambassador, Card::Captain];
+		coup.set_score(vec![String::from("StaticBot")]);
+
+		assert_eq!(coup.score[0], (String::from("StaticBot"), 1.0));
+		assert_eq!(coup.score[1], (String::from("StaticBot 2"), -0.2));
+		assert_eq!(coup.score[6], (String::from("StaticBot 7"), 0.0));
+		assert_eq!(coup.score[7], (String::from("StaticBot 8"), 0.0));
+
+		// Eight bots at a table of eight, one winner
+		coup.set_table_size(8);
+		coup.setup();
+
+		coup.set_score(vec![String::from("StaticBot")]);
+
+		assert_eq!(coup.score[0], (String::from("StaticBot"), 2.0));
+		assert_eq!(coup.score[1], (String::from("StaticBot 2"), -0.2 - 1.0 / 7.0));
+		assert_eq!(coup.score[7], (String::from("StaticBot 8"), -1.0 / 7.0));
+	}
+
+	#[test]
+	fn test_swap_card() {
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+		coup.setup();
+		coup.playing_bots = vec![0, 1];
+
+		coup.bots[0].cards = vec![Card::Ambassador, Card::Ambassador];
+		coup.bots[1].cards = vec![Card::Assassin, Card::Captain];
+		coup.deck = vec![Card::Ambassador, Card::Captain];
 
 		assert_eq!(coup.bots[0].cards, vec![Card::Ambassador, Card::Ambassador]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Assassin, Card::Captain]);
@@ -1773,7 +3332,7 @@ mod tests {
 				String::from("ActionChallengeBot")
 			}
 			fn on_challenge_action_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -1787,7 +3346,7 @@ mod tests {
 				String::from("ChallengeCounterBot")
 			}
 			fn on_challenge_counter_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -1801,7 +3360,7 @@ mod tests {
 				String::from("CounterBot")
 			}
 			fn on_counter(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -1814,7 +3373,7 @@ mod tests {
 			fn get_name(&self) -> String {
 				String::from("AssassinationBot")
 			}
-			fn on_turn(&self, _context: &Context) -> Action {
+			fn on_turn(&mut self, _context: &Context) -> Action {
 				Action::Assassination(String::from("StaticBot"))
 			}
 		}
@@ -1823,7 +3382,7 @@ mod tests {
 			fn get_name(&self) -> String {
 				String::from("CoupBot")
 			}
-			fn on_turn(&self, _context: &Context) -> Action {
+			fn on_turn(&mut self, _context: &Context) -> Action {
 				Action::Coup(String::from("StaticBot"))
 			}
 		}
@@ -1832,7 +3391,7 @@ mod tests {
 			fn get_name(&self) -> String {
 				String::from("ForeignAidBot")
 			}
-			fn on_turn(&self, _context: &Context) -> Action {
+			fn on_turn(&mut self, _context: &Context) -> Action {
 				Action::ForeignAid
 			}
 		}
@@ -1841,7 +3400,7 @@ mod tests {
 			fn get_name(&self) -> String {
 				String::from("SwappingBot")
 			}
-			fn on_turn(&self, _context: &Context) -> Action {
+			fn on_turn(&mut self, _context: &Context) -> Action {
 				Action::Swapping
 			}
 		}
@@ -1850,7 +3409,7 @@ mod tests {
 			fn get_name(&self) -> String {
 				String::from("IncomeBot")
 			}
-			fn on_turn(&self, _context: &Context) -> Action {
+			fn on_turn(&mut self, _context: &Context) -> Action {
 				Action::Income
 			}
 		}
@@ -1859,7 +3418,7 @@ mod tests {
 			fn get_name(&self) -> String {
 				String::from("StealingBot")
 			}
-			fn on_turn(&self, _context: &Context) -> Action {
+			fn on_turn(&mut self, _context: &Context) -> Action {
 				Action::Stealing(String::from("StaticBot"))
 			}
 		}
@@ -1868,7 +3427,7 @@ mod tests {
 			fn get_name(&self) -> String {
 				String::from("TaxBot")
 			}
-			fn on_turn(&self, _context: &Context) -> Action {
+			fn on_turn(&mut self, _context: &Context) -> Action {
 				Action::Tax
 			}
 		}
@@ -1909,7 +3468,15 @@ mod tests {
 				History::ChallengeAssassin {
 					by: String::from("ActionChallengeBot"),
 					target: String::from("AssassinationBot"),
-				}
+				},
+				History::ChallengeSuccessful {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("AssassinationBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("AssassinationBot"),
+					card: Card::Captain,
+				},
 			]
 		);
 
@@ -1925,6 +3492,7 @@ mod tests {
 		coup.setup();
 		coup.bots[0].cards = vec![Card::Duke, Card::Captain];
 		coup.bots[0].coins = 4;
+		coup.bots[1].cards = vec![Card::Captain, Card::Duke];
 		coup.bots[2].cards = vec![Card::Ambassador, Card::Assassin];
 		coup.playing_bots = vec![0, 1, 2, 3, 4, 5];
 		coup.turn = 0;
@@ -1950,7 +3518,22 @@ mod tests {
 				History::ChallengeCaptain {
 					by: String::from("ActionChallengeBot"),
 					target: String::from("StealingBot"),
-				}
+				},
+				History::ChallengeUnsuccessful {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("StealingBot"),
+				},
+				History::RevealCard {
+					by: String::from("StealingBot"),
+					card: Card::Captain,
+				},
+				History::LoseInfluence {
+					by: String::from("ActionChallengeBot"),
+					card: Card::Duke,
+				},
+				History::SwapCard {
+					by: String::from("StealingBot"),
+				},
 			]
 		);
 
@@ -2033,6 +3616,14 @@ mod tests {
 					by: String::from("ChallengeCounterBot"),
 					target: String::from("CounterBot"),
 				},
+				History::CounterChallengeSuccessful {
+					by: String::from("ChallengeCounterBot"),
+					target: String::from("CounterBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("CounterBot"),
+					card: Card::Assassin,
+				},
 			]
 		);
 
@@ -2049,6 +3640,7 @@ mod tests {
 		coup.bots[0].cards = vec![Card::Duke, Card::Captain];
 		coup.bots[0].coins = 4;
 		coup.bots[1].cards = vec![Card::Duke, Card::Assassin];
+		coup.bots[2].cards = vec![Card::Captain, Card::Duke];
 		coup.playing_bots = vec![0, 1, 2, 3, 4, 5];
 		coup.turn = 0;
 		coup.history = vec![];
@@ -2076,6 +3668,18 @@ mod tests {
 					by: String::from("ChallengeCounterBot"),
 					target: String::from("CounterBot"),
 				},
+				History::CounterChallengeUnsuccessful {
+					by: String::from("ChallengeCounterBot"),
+					target: String::from("CounterBot"),
+				},
+				History::RevealCard {
+					by: String::from("CounterBot"),
+					card: Card::Duke,
+				},
+				History::LoseInfluence {
+					by: String::from("ChallengeCounterBot"),
+					card: Card::Duke,
+				},
 			]
 		);
 
@@ -2107,10 +3711,16 @@ mod tests {
 		assert_eq!(coup.bots[5].cards.len(), 2);
 		assert_eq!(
 			coup.history,
-			vec![History::ActionAssassination {
-				by: String::from("AssassinationBot"),
-				target: String::from("StaticBot"),
-			},]
+			vec![
+				History::ActionAssassination {
+					by: String::from("AssassinationBot"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Assassin,
+				},
+			]
 		);
 
 		// Coup
@@ -2141,10 +3751,16 @@ mod tests {
 		assert_eq!(coup.bots[5].cards.len(), 2);
 		assert_eq!(
 			coup.history,
-			vec![History::ActionCoup {
-				by: String::from("CoupBot"),
-				target: String::from("StaticBot"),
-			},]
+			vec![
+				History::ActionCoup {
+					by: String::from("CoupBot"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Assassin,
+				},
+			]
 		);
 
 		// ForeignAid
@@ -2208,9 +3824,14 @@ mod tests {
 		assert_eq!(coup.bots[5].cards.len(), 2);
 		assert_eq!(
 			coup.history,
-			vec![History::ActionSwapping {
-				by: String::from("SwappingBot"),
-			},]
+			vec![
+				History::ActionSwapping {
+					by: String::from("SwappingBot"),
+				},
+				History::ExchangeCards {
+					by: String::from("SwappingBot"),
+				},
+			]
 		);
 
 		// Income
@@ -2357,7 +3978,7 @@ mod tests {
 				String::from("ActionChallengeBot")
 			}
 			fn on_challenge_action_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -2371,7 +3992,7 @@ mod tests {
 				String::from("ChallengeCounterBot")
 			}
 			fn on_challenge_counter_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -2385,7 +4006,7 @@ mod tests {
 				String::from("CounterBot")
 			}
 			fn on_counter(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -2425,10 +4046,20 @@ mod tests {
 		assert_eq!(coup.bots[5].cards.len(), 2);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeAssassin {
-				by: String::from("ActionChallengeBot"),
-				target: String::from("StaticBot"),
-			}]
+			vec![
+				History::ChallengeAssassin {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeSuccessful {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Captain,
+				},
+			]
 		);
 
 		// Successful counter
@@ -2507,7 +4138,19 @@ mod tests {
 				History::CounterChallengeContessa {
 					by: String::from("ChallengeCounterBot"),
 					target: String::from("CounterBot"),
-				}
+				},
+				History::CounterChallengeSuccessful {
+					by: String::from("ChallengeCounterBot"),
+					target: String::from("CounterBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("CounterBot"),
+					card: Card::Assassin,
+				},
+				History::LoseInfluence {
+					by: String::from("CounterBot"),
+					card: Card::Captain,
+				},
 			]
 		);
 
@@ -2553,6 +4196,7 @@ mod tests {
 		coup.setup();
 		coup.bots[0].cards = vec![Card::Assassin, Card::Captain];
 		coup.bots[0].coins = 4;
+		coup.bots[1].cards = vec![Card::Duke, Card::Captain];
 		coup.bots[3].cards = vec![Card::Ambassador, Card::Assassin];
 		coup.playing_bots = vec![0, 1, 2, 3, 4, 5];
 		coup.turn = 0;
@@ -2572,10 +4216,31 @@ mod tests {
 		assert_eq!(coup.bots[5].cards.len(), 2);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeAssassin {
-				by: String::from("ActionChallengeBot"),
-				target: String::from("StaticBot"),
-			}]
+			vec![
+				History::ChallengeAssassin {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeUnsuccessful {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Assassin,
+				},
+				History::LoseInfluence {
+					by: String::from("ActionChallengeBot"),
+					card: Card::Captain,
+				},
+				History::SwapCard {
+					by: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot 3"),
+					card: Card::Assassin,
+				},
+			]
 		);
 
 		// Unsuccessful counter challenge
@@ -2618,7 +4283,19 @@ mod tests {
 				History::CounterChallengeContessa {
 					by: String::from("ChallengeCounterBot"),
 					target: String::from("CounterBot"),
-				}
+				},
+				History::CounterChallengeUnsuccessful {
+					by: String::from("ChallengeCounterBot"),
+					target: String::from("CounterBot"),
+				},
+				History::RevealCard {
+					by: String::from("CounterBot"),
+					card: Card::Contessa,
+				},
+				History::LoseInfluence {
+					by: String::from("ChallengeCounterBot"),
+					card: Card::Ambassador,
+				},
 			]
 		);
 	}
@@ -2631,7 +4308,7 @@ mod tests {
 				String::from("ActionChallengeBot")
 			}
 			fn on_challenge_action_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -2645,7 +4322,7 @@ mod tests {
 				String::from("ChallengeCounterBot")
 			}
 			fn on_challenge_counter_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -2659,7 +4336,7 @@ mod tests {
 				String::from("CounterBot")
 			}
 			fn on_counter(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -2700,10 +4377,20 @@ mod tests {
 		assert_eq!(coup.bots[5].cards.len(), 2);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeCaptain {
-				by: String::from("ActionChallengeBot"),
-				target: String::from("StaticBot"),
-			}]
+			vec![
+				History::ChallengeCaptain {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeSuccessful {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Assassin,
+				},
+			]
 		);
 
 		// Successful counter
@@ -2784,7 +4471,15 @@ mod tests {
 				History::CounterChallengeCaptainAmbassedor {
 					by: String::from("ChallengeCounterBot"),
 					target: String::from("CounterBot"),
-				}
+				},
+				History::CounterChallengeSuccessful {
+					by: String::from("ChallengeCounterBot"),
+					target: String::from("CounterBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("CounterBot"),
+					card: Card::Assassin,
+				},
 			]
 		);
 
@@ -2831,6 +4526,7 @@ mod tests {
 		coup.setup();
 		coup.bots[0].cards = vec![Card::Assassin, Card::Captain];
 		coup.bots[0].coins = 4;
+		coup.bots[1].cards = vec![Card::Duke, Card::Ambassador];
 		coup.bots[3].cards = vec![Card::Duke, Card::Assassin];
 		coup.playing_bots = vec![0, 1, 2, 3, 4, 5];
 		coup.turn = 0;
@@ -2851,10 +4547,27 @@ mod tests {
 		assert_eq!(coup.bots[5].cards.len(), 2);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeCaptain {
-				by: String::from("ActionChallengeBot"),
-				target: String::from("StaticBot"),
-			}]
+			vec![
+				History::ChallengeCaptain {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeUnsuccessful {
+					by: String::from("ActionChallengeBot"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Captain,
+				},
+				History::LoseInfluence {
+					by: String::from("ActionChallengeBot"),
+					card: Card::Ambassador,
+				},
+				History::SwapCard {
+					by: String::from("StaticBot"),
+				},
+			]
 		);
 
 		// Unsuccessful counter challenge with Captain
@@ -2898,7 +4611,19 @@ mod tests {
 				History::CounterChallengeCaptainAmbassedor {
 					by: String::from("ChallengeCounterBot"),
 					target: String::from("CounterBot"),
-				}
+				},
+				History::CounterChallengeUnsuccessful {
+					by: String::from("ChallengeCounterBot"),
+					target: String::from("CounterBot"),
+				},
+				History::RevealCard {
+					by: String::from("CounterBot"),
+					card: Card::Captain,
+				},
+				History::LoseInfluence {
+					by: String::from("ChallengeCounterBot"),
+					card: Card::Ambassador,
+				},
 			]
 		);
 
@@ -2943,7 +4668,19 @@ mod tests {
 				History::CounterChallengeCaptainAmbassedor {
 					by: String::from("ChallengeCounterBot"),
 					target: String::from("CounterBot"),
-				}
+				},
+				History::CounterChallengeUnsuccessful {
+					by: String::from("ChallengeCounterBot"),
+					target: String::from("CounterBot"),
+				},
+				History::RevealCard {
+					by: String::from("CounterBot"),
+					card: Card::Ambassador,
+				},
+				History::LoseInfluence {
+					by: String::from("ChallengeCounterBot"),
+					card: Card::Ambassador,
+				},
 			]
 		);
 	}
@@ -2957,7 +4694,7 @@ mod tests {
 				String::from("TestBot")
 			}
 			fn on_challenge_action_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3006,7 +4743,7 @@ mod tests {
 				String::from("TestBot")
 			}
 			fn on_challenge_action_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3054,7 +4791,7 @@ mod tests {
 				String::from("TestBot")
 			}
 			fn on_counter(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3068,7 +4805,7 @@ mod tests {
 				String::from("ChallengeBot")
 			}
 			fn on_challenge_counter_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3107,7 +4844,7 @@ mod tests {
 				String::from("TestBot")
 			}
 			fn on_counter(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3121,7 +4858,7 @@ mod tests {
 				String::from("ChallengeBot")
 			}
 			fn on_challenge_counter_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3162,7 +4899,7 @@ mod tests {
 				format!("TestBot{}", self.calls.borrow().join(","))
 			}
 			fn on_challenge_action_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3261,7 +4998,7 @@ mod tests {
 				format!("TestBot{}", self.calls.borrow().join(","))
 			}
 			fn on_challenge_action_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3278,7 +5015,7 @@ mod tests {
 				format!("ChallengeBot{}", self.calls.borrow().join(","))
 			}
 			fn on_challenge_action_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3362,7 +5099,7 @@ mod tests {
 				format!("TestBot{}", self.calls.borrow().join(","))
 			}
 			fn on_challenge_counter_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3464,7 +5201,7 @@ mod tests {
 				format!("TestBot{}", self.calls.borrow().join(","))
 			}
 			fn on_challenge_counter_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3484,7 +5221,7 @@ mod tests {
 				format!("ChallengeBot{}", self.calls.borrow().join(","))
 			}
 			fn on_challenge_counter_round(
-				&self,
+				&mut self,
 				_action: &Action,
 				_by: String,
 				_context: &Context,
@@ -3579,15 +5316,25 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, true);
+		assert!(result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Duke]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador, Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeAssassin {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::ChallengeAssassin {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeSuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Captain,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3601,15 +5348,25 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, true);
+		assert!(result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Duke]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador, Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeAmbassador {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::ChallengeAmbassador {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeSuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Captain,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3623,15 +5380,25 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, true);
+		assert!(result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Duke]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador, Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeCaptain {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::ChallengeCaptain {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeSuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Assassin,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3645,15 +5412,25 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, true);
+		assert!(result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Captain]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador, Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeDuke {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::ChallengeDuke {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeSuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Captain,
+				},
+			]
 		);
 	}
 
@@ -3672,15 +5449,29 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, false);
+		assert!(!result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Assassin, Card::Captain]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeAssassin {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::ChallengeAssassin {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeUnsuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Assassin,
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot 2"),
+					card: Card::Ambassador,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3694,15 +5485,29 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, false);
+		assert!(!result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Assassin, Card::Ambassador]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeAmbassador {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::ChallengeAmbassador {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeUnsuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Ambassador,
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot 2"),
+					card: Card::Ambassador,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3716,15 +5521,29 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, false);
+		assert!(!result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Assassin, Card::Captain]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeCaptain {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::ChallengeCaptain {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeUnsuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Captain,
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot 2"),
+					card: Card::Ambassador,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3738,15 +5557,29 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, false);
+		assert!(!result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Duke, Card::Captain]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::ChallengeDuke {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::ChallengeDuke {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::ChallengeUnsuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Duke,
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot 2"),
+					card: Card::Ambassador,
+				},
+			]
 		);
 		coup.history = vec![];
 	}
@@ -3766,15 +5599,25 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, true);
+		assert!(result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Duke]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador, Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::CounterChallengeContessa {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::CounterChallengeContessa {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::CounterChallengeSuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Captain,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3788,21 +5631,31 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, true);
+		assert!(result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Assassin]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador, Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::CounterChallengeDuke {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
-		);
-		coup.history = vec![];
-
-		// Stealing
-		coup.bots[0].cards = vec![Card::Assassin, Card::Contessa];
-		coup.bots[1].cards = vec![Card::Ambassador, Card::Ambassador];
+			vec![
+				History::CounterChallengeDuke {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::CounterChallengeSuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Captain,
+				},
+			]
+		);
+		coup.history = vec![];
+
+		// Stealing
+		coup.bots[0].cards = vec![Card::Assassin, Card::Contessa];
+		coup.bots[1].cards = vec![Card::Ambassador, Card::Ambassador];
 
 		let result = coup.resolve_counter_challenge(
 			Counter::Stealing,
@@ -3810,15 +5663,25 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, true);
+		assert!(result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Assassin]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador, Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::CounterChallengeCaptainAmbassedor {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::CounterChallengeCaptainAmbassedor {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::CounterChallengeSuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot"),
+					card: Card::Contessa,
+				},
+			]
 		);
 	}
 
@@ -3837,15 +5700,29 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, false);
+		assert!(!result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Assassin, Card::Contessa]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::CounterChallengeContessa {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::CounterChallengeContessa {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::CounterChallengeUnsuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Contessa,
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot 2"),
+					card: Card::Ambassador,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3859,15 +5736,29 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, false);
+		assert!(!result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Duke, Card::Contessa]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::CounterChallengeDuke {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::CounterChallengeDuke {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::CounterChallengeUnsuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Duke,
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot 2"),
+					card: Card::Ambassador,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3881,15 +5772,29 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, false);
+		assert!(!result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Duke, Card::Captain]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::CounterChallengeCaptainAmbassedor {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::CounterChallengeCaptainAmbassedor {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::CounterChallengeUnsuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Captain,
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot 2"),
+					card: Card::Ambassador,
+				},
+			]
 		);
 		coup.history = vec![];
 
@@ -3903,15 +5808,29 @@ mod tests {
 			String::from("StaticBot 2"),
 		);
 
-		assert_eq!(result, false);
+		assert!(!result);
 		assert_eq!(coup.bots[0].cards, vec![Card::Duke, Card::Ambassador]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Ambassador]);
 		assert_eq!(
 			coup.history,
-			vec![History::CounterChallengeCaptainAmbassedor {
-				by: String::from("StaticBot 2"),
-				target: String::from("StaticBot")
-			}]
+			vec![
+				History::CounterChallengeCaptainAmbassedor {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::CounterChallengeUnsuccessful {
+					by: String::from("StaticBot 2"),
+					target: String::from("StaticBot"),
+				},
+				History::RevealCard {
+					by: String::from("StaticBot"),
+					card: Card::Ambassador,
+				},
+				History::LoseInfluence {
+					by: String::from("StaticBot 2"),
+					card: Card::Ambassador,
+				},
+			]
 		);
 	}
 
@@ -4059,6 +5978,146 @@ mod tests {
 		assert_eq!(coup.discard_pile, vec![Card::Duke]);
 	}
 
+	#[test]
+	fn test_action_config() {
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+		coup.set_config(GameConfig {
+			coup_cost: 5,
+			assassination_cost: 4,
+			stealing_amount: 3,
+			tax_amount: 4,
+			..GameConfig::default()
+		});
+		coup.setup();
+		coup.bots[0].cards = vec![Card::Ambassador, Card::Duke];
+		coup.bots[1].cards = vec![Card::Assassin, Card::Captain];
+		coup.playing_bots = vec![0, 1];
+		coup.bots[0].coins = 5;
+		coup.bots[1].coins = 5;
+
+		coup.action_couping(String::from("StaticBot 2"));
+
+		assert_eq!(coup.bots[0].coins, 0);
+		assert_eq!(coup.bots[1].cards.len(), 1);
+
+		coup.action_tax();
+		coup.action_stealing(String::from("StaticBot 2"));
+
+		assert_eq!(coup.bots[0].coins, 7);
+		assert_eq!(coup.bots[1].coins, 2);
+
+		coup.bots[0].coins = 3;
+		coup.action_assassination(String::from("StaticBot 2"));
+
+		assert_eq!(coup.bots[0].cards.len(), 1);
+		assert_eq!(coup.bots[0].coins, 3);
+		assert_eq!(coup.bots[1].cards.len(), 1);
+	}
+
+	#[test]
+	fn test_treasury() {
+		let mut coup = Coup::new(vec![
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+		]);
+		coup.setup();
+		coup.playing_bots = vec![0, 1, 2];
+
+		assert_eq!(coup.treasury, 44);
+		assert_eq!(coup.get_context(String::from("StaticBot")).treasury, 44);
+
+		coup.bots[0].coins = 7;
+		coup.action_couping(String::from("StaticBot 2"));
+		coup.bots[0].coins = 3;
+		coup.action_assassination(String::from("StaticBot 3"));
+
+		assert_eq!(coup.bots[0].coins, 0);
+		assert_eq!(coup.treasury, 54);
+
+		coup.treasury = 4;
+		coup.action_tax();
+		coup.action_income();
+
+		assert_eq!(coup.bots[0].coins, 4);
+		assert_eq!(coup.treasury, 0);
+
+		coup.action_foraign_aid();
+		coup.action_income();
+
+		assert_eq!(coup.bots[0].coins, 4);
+		assert_eq!(coup.treasury, 0);
+
+		// An exiled bot gives its coins back
+		coup.bots[2].coins = 5;
+		coup.card_loss(String::from("StaticBot 3"));
+
+		assert_eq!(coup.bots[2].cards, vec![]);
+		assert_eq!(coup.bots[2].coins, 0);
+		assert_eq!(coup.treasury, 5);
+	}
+
+	#[test]
+	fn test_setup_small_treasury() {
+		let mut coup = Coup::new(vec![
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+		]);
+		coup.set_config(GameConfig {
+			treasury_coins: 5,
+			..GameConfig::default()
+		});
+		coup.setup();
+
+		assert_eq!(coup.bots[coup.playing_bots[0]].coins, 2);
+		assert_eq!(coup.bots[coup.playing_bots[1]].coins, 2);
+		assert_eq!(coup.bots[coup.playing_bots[2]].coins, 1);
+		assert_eq!(coup.treasury, 0);
+	}
+
+	#[test]
+	fn test_play_coin_conservation() {
+		for seed in 0..20 {
+			let mut coup = Coup::new_with_seed(
+				vec![
+					Box::new(RandomBot),
+					Box::new(HonestBot),
+					Box::new(RandomBot),
+					Box::new(StaticBot),
+				],
+				seed,
+			);
+			coup.set_quiet(true);
+			coup.set_reformation(Reformation::all());
+			coup.play();
+
+			let coins = coup.bots.iter().map(|bot| bot.coins).sum::<u8>();
+			assert_eq!(coins + coup.treasury + coup.treasury_reserve, 50);
+		}
+	}
+
+	#[test]
+	fn test_play_max_moves() {
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+		coup.set_quiet(true);
+		coup.set_config(GameConfig {
+			max_moves: 10,
+			auto_coup_coins: 100,
+			..GameConfig::default()
+		});
+		coup.play();
+
+		assert_eq!(coup.moves, 10);
+		assert_eq!(
+			coup.get_score(),
+			&vec![
+				(String::from("StaticBot"), 0.0),
+				(String::from("StaticBot 2"), 0.0)
+			]
+		);
+	}
+
 	#[test]
 	fn test_action_foraign_aid() {
 		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
@@ -4086,6 +6145,12 @@ mod tests {
 		assert_eq!(coup.bots[0].cards, vec![Card::Ambassador, Card::Duke]);
 		assert_eq!(coup.bots[1].cards, vec![Card::Assassin, Card::Captain]);
 		assert_eq!(coup.deck.len(), 2);
+		assert_eq!(
+			coup.history,
+			vec![History::ExchangeCards {
+				by: String::from("StaticBot"),
+			}]
+		);
 	}
 
 	#[test]
@@ -4096,7 +6161,7 @@ mod tests {
 				String::from("TestBot")
 			}
 			fn on_swapping_cards(
-				&self,
+				&mut self,
 				new_cards: [Card; 2],
 				context: &Context,
 			) -> [Card; 2] {
@@ -4127,7 +6192,7 @@ mod tests {
 				String::from("TestBot")
 			}
 			fn on_swapping_cards(
-				&self,
+				&mut self,
 				_new_cards: [Card; 2],
 				_context: &Context,
 			) -> [Card; 2] {
@@ -4232,4 +6297,304 @@ mod tests {
 		assert_eq!(coup.bots[0].coins, 5);
 		assert_eq!(coup.bots[1].coins, 2);
 	}
+
+	#[test]
+	fn test_new_reformation_deck() {
+		let deck =
+			Coup::new_table_deck_with_rng(&Reformation::all(), 6, &mut thread_rng());
+		assert_eq!(deck.len(), 15);
+		assert_eq!(
+			deck.iter().filter(|&card| card == &Card::Inquisitor).count(),
+			3
+		);
+		assert_eq!(
+			deck.iter().filter(|&card| card == &Card::Ambassador).count(),
+			0
+		);
+	}
+
+	#[test]
+	fn test_setup_reformation() {
+		let mut coup = Coup::new(vec![
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+		]);
+		coup.set_reformation(Reformation {
+			factions: true,
+			inquisitor: false,
+		});
+		coup.treasury_reserve = 4;
+		coup.setup();
+
+		assert_eq!(
+			coup.bots[coup.playing_bots[0]].allegiance,
+			Some(Allegiance::Loyalist)
+		);
+		assert_eq!(
+			coup.bots[coup.playing_bots[1]].allegiance,
+			Some(Allegiance::Reformist)
+		);
+		assert_eq!(
+			coup.bots[coup.playing_bots[2]].allegiance,
+			Some(Allegiance::Loyalist)
+		);
+		assert_eq!(coup.treasury_reserve, 0);
+		assert!(coup.deck.iter().all(|card| card != &Card::Inquisitor));
+
+		coup.set_reformation(Reformation::default());
+		coup.setup();
+
+		assert!(coup.bots.iter().all(|bot| bot.allegiance.is_none()));
+	}
+
+	#[test]
+	fn test_same_faction() {
+		let mut coup = Coup::new(vec![
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+		]);
+		coup.setup();
+		coup.playing_bots = vec![0, 1, 2];
+		coup.bots[0].allegiance = Some(Allegiance::Loyalist);
+		coup.bots[1].allegiance = Some(Allegiance::Loyalist);
+		coup.bots[2].allegiance = Some(Allegiance::Reformist);
+
+		assert!(coup.same_faction("StaticBot", "StaticBot 2"));
+		assert!(!coup.same_faction("StaticBot", "StaticBot 3"));
+
+		// Once everyone is on the same side anyone can be targeted
+		coup.bots[2].cards = vec![];
+		assert!(!coup.same_faction("StaticBot", "StaticBot 2"));
+
+		coup.bots[0].allegiance = None;
+		coup.bots[1].allegiance = None;
+		assert!(!coup.same_faction("StaticBot", "StaticBot 2"));
+	}
+
+	#[test]
+	fn test_game_loop_same_faction() {
+		struct CoupBot;
+		impl BotInterface for CoupBot {
+			fn get_name(&self) -> String {
+				String::from("CoupBot")
+			}
+			fn on_turn(&mut self, _context: &Context) -> Action {
+				Action::Coup(String::from("StaticBot"))
+			}
+		}
+
+		let mut coup = Coup::new(vec![
+			Box::new(CoupBot),
+			Box::new(StaticBot),
+			Box::new(StaticBot),
+		]);
+		coup.set_reformation(Reformation::all());
+		coup.setup();
+		coup.bots[0].cards = vec![Card::Duke, Card::Captain];
+		coup.bots[0].coins = 9;
+		coup.bots[0].allegiance = Some(Allegiance::Loyalist);
+		coup.bots[1].allegiance = Some(Allegiance::Loyalist);
+		coup.bots[2].allegiance = Some(Allegiance::Reformist);
+		coup.playing_bots = vec![0, 1, 2];
+		coup.turn = 0;
+		coup.history = vec![];
+
+		coup.game_loop();
+
+		assert_eq!(coup.bots[0].cards.len(), 1);
+		assert_eq!(coup.bots[0].coins, 9);
+		assert_eq!(coup.bots[1].cards.len(), 2);
+		assert_eq!(
+			coup.history[0],
+			History::Penalty {
+				by: String::from("CoupBot"),
+				reason: String::from("it tried to coup a bot of its own faction"),
+			}
+		);
+	}
+
+	#[test]
+	fn test_action_conversion() {
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+		coup.set_reformation(Reformation::all());
+		coup.setup();
+		coup.bots[0].allegiance = Some(Allegiance::Loyalist);
+		coup.bots[1].allegiance = Some(Allegiance::Reformist);
+		coup.playing_bots = vec![0, 1];
+
+		coup.action_conversion(String::from("StaticBot"));
+
+		assert_eq!(coup.bots[0].coins, 1);
+		assert_eq!(coup.bots[0].allegiance, Some(Allegiance::Reformist));
+		assert_eq!(coup.treasury_reserve, 1);
+
+		coup.bots[0].coins = 2;
+		coup.action_conversion(String::from("StaticBot 2"));
+
+		assert_eq!(coup.bots[0].coins, 0);
+		assert_eq!(coup.bots[1].allegiance, Some(Allegiance::Loyalist));
+		assert_eq!(coup.treasury_reserve, 3);
+	}
+
+	#[test]
+	fn test_action_conversion_insufficient_funds() {
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+		coup.set_reformation(Reformation::all());
+		coup.setup();
+		coup.bots[0].cards = vec![Card::Duke, Card::Captain];
+		coup.bots[0].coins = 1;
+		coup.bots[1].allegiance = Some(Allegiance::Reformist);
+		coup.playing_bots = vec![0, 1];
+
+		coup.action_conversion(String::from("StaticBot 2"));
+
+		assert_eq!(coup.bots[0].cards.len(), 1);
+		assert_eq!(coup.bots[0].coins, 1);
+		assert_eq!(coup.bots[1].allegiance, Some(Allegiance::Reformist));
+		assert_eq!(coup.treasury_reserve, 0);
+	}
+
+	#[test]
+	fn test_action_embezzlement() {
+		let mut coup = Coup::new(vec![Box::new(StaticBot), Box::new(StaticBot)]);
+		coup.set_reformation(Reformation::all());
+		coup.setup();
+		coup.playing_bots = vec![0, 1];
+		coup.treasury_reserve = 5;
+
+		coup.action_embezzlement();
+
+		assert_eq!(coup.bots[0].coins, 7);
+		assert_eq!(coup.treasury_reserve, 0);
+	}
+
+	#[test]
+	fn test_action_swapping_inquisitor() {
+		struct TestBot;
+		impl BotInterface for TestBot {
+			fn get_name(&self) -> String {
+				String::from("TestBot")
+			}
+			fn on_exchanging_card(
+				&mut self,
+				_new_card: Card,
+				context: &Context,
+			) -> Card {
+				context.cards[0]
+			}
+		}
+
+		let mut coup = Coup::new(vec![Box::new(TestBot), Box::new(StaticBot)]);
+		coup.set_reformation(Reformation::all());
+		coup.setup();
+		coup.bots[0].cards = vec![Card::Inquisitor, Card::Duke];
+		coup.playing_bots = vec![0, 1];
+		coup.deck = vec![Card::Captain, Card::Assassin];
+
+		coup.action_swapping();
+
+		assert_eq!(coup.bots[0].cards, vec![Card::Duke, Card::Assassin]);
+		assert_eq!(coup.deck.len(), 2);
+		assert!(coup.deck.contains(&Card::Inquisitor));
+		assert_eq!(
+			coup.history,
+			vec![History::ExchangeCards {
+				by: String::from("TestBot"),
+			}]
+		);
+	}
+
+	#[test]
+	fn test_action_swapping_inquisitor_faulty_bot() {
+		struct TestBot;
+		impl BotInterface for TestBot {
+			fn get_name(&self) -> String {
+				String::from("TestBot")
+			}
+			fn on_exchanging_card(
+				&mut self,
+				_new_card: Card,
+				_context: &Context,
+			) -> Card {
+				Card::Contessa
+			}
+		}
+
+		let mut coup = Coup::new(vec![Box::new(TestBot), Box::new(StaticBot)]);
+		coup.set_reformation(Reformation::all());
+		coup.setup();
+		coup.bots[0].cards = vec![Card::Inquisitor, Card::Duke];
+		coup.playing_bots = vec![0, 1];
+		coup.deck = vec![Card::Captain, Card::Assassin];
+
+		coup.action_swapping();
+
+		assert_eq!(coup.bots[0].cards.len(), 1);
+		assert_eq!(coup.deck.len(), 2);
+		assert!(coup.deck.contains(&Card::Assassin));
+	}
+
+	#[test]
+	fn test_action_examine() {
+		struct TestBot;
+		impl BotInterface for TestBot {
+			fn get_name(&self) -> String {
+				String::from("TestBot")
+			}
+			fn on_examined_card(
+				&mut self,
+				card: Card,
+				_target: String,
+				_context: &Context,
+			) -> bool {
+				card == Card::Duke
+			}
+		}
+
+		let mut coup = Coup::new(vec![Box::new(TestBot), Box::new(StaticBot)]);
+		coup.set_reformation(Reformation::all());
+		coup.setup();
+		coup.bots[1].cards = vec![Card::Duke, Card::Captain];
+		coup.playing_bots = vec![0, 1];
+		coup.deck = vec![Card::Assassin];
+		coup.history = vec![];
+
+		coup.action_examine(String::from("StaticBot"));
+
+		assert_eq!(coup.bots[1].cards.len(), 2);
+		assert!(coup.bots[1].cards.contains(&Card::Captain));
+		assert_eq!(coup.deck.len(), 1);
+		assert_eq!(
+			coup.history,
+			vec![
+				History::ExamineCard {
+					by: String::from("TestBot"),
+					target: String::from("StaticBot"),
+				},
+				History::ForceExchange {
+					by: String::from("TestBot"),
+					target: String::from("StaticBot"),
+				},
+				History::SwapCard {
+					by: String::from("StaticBot"),
+				},
+			]
+		);
+
+		coup.bots[1].cards = vec![Card::Captain, Card::Duke];
+		coup.history = vec![];
+
+		coup.action_examine(String::from("StaticBot"));
+
+		assert_eq!(coup.bots[1].cards, vec![Card::Captain, Card::Duke]);
+		assert_eq!(
+			coup.history,
+			vec![History::ExamineCard {
+				by: String::from("TestBot"),
+				target: String::from("StaticBot"),
+			}]
+		);
+	}
 }
@@ -1,19 +1,338 @@
-// running MCTSBot vs HonestBot for testing
-// replace bots here to compare performance
 use coup::{
-    bots::{HonestBot, RandomBot, StaticBot, DuelBot, mcts_bot::MctsBot},
-    Coup,
+	bots::BotRegistry, game_log::JsonLinesSink, replay::Replay,
+	tournament::Tournament, Coup, GameConfig, Score,
 };
+use rand::{thread_rng, Rng};
+use std::{
+	env,
+	fs::File,
+	io::{self, BufReader},
+	process,
+};
+
+const USAGE: &str = "Play a game of Coup between bots
+
+Usage: coup [MODE] [OPTIONS] [BOTS]...
+       coup replay <FILE>
+
+Modes:
+  play        Play a single game (default)
+  loop        Play many games and tally up the score
+  tournament  Play many games across all cores and tally up the score
+  replay      Step through the games of a jsonl game log
+
+Bots:
+  The names of the bots at the table, repeat a name to add a bot more than once.
+  Defaults to: MCTSBot HonestBot
+
+Options:
+  -g, --games <N>      The amount of games to play in loop and tournament mode [default: 1000]
+  -s, --seed <SEED>    The seed to play with so games can be reproduced
+  -t, --threads <N>    The amount of threads in tournament mode [default: all cores]
+  -q, --quiet          Turn off the console rendering and only print the score
+  -f, --format <FMT>   The output format: console or jsonl [default: console]
+  -p, --players <N>    The most bots seated at a table, up to 10 [default: 6]
+  -r, --reformation    Play with the factions of the Reformation expansion
+  -i, --inquisitor     Play with the Inquisitor instead of the Ambassador
+  -2, --two-player     Play the official two-player variant at tables of two
+  -l, --list           List all available bots
+  -h, --help           Print this help";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+	Play,
+	Loop,
+	Tournament,
+	Replay,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+	Console,
+	JsonLines,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Args {
+	mode: Mode,
+	replay_file: Option<String>,
+	bots: Vec<String>,
+	games: u64,
+	seed: Option<u64>,
+	threads: Option<usize>,
+	quiet: bool,
+	format: Format,
+	config: GameConfig,
+	list: bool,
+	help: bool,
+}
+
+impl Default for Args {
+	fn default() -> Self {
+		Self {
+			mode: Mode::Play,
+			replay_file: None,
+			bots: vec![],
+			games: 1_000,
+			seed: None,
+			threads: None,
+			quiet: false,
+			format: Format::Console,
+			config: GameConfig::default(),
+			list: false,
+			help: false,
+		}
+	}
+}
+
+fn parse_value<T: std::str::FromStr>(
+	flag: &str,
+	value: Option<String>,
+) -> Result<T, String> {
+	let value = value.ok_or(format!("{} needs a value", flag))?;
+	value
+		.parse::<T>()
+		.map_err(|_| format!("\"{}\" is not a valid value for {}", value, flag))
+}
+
+fn parse_args(
+	args: impl IntoIterator<Item = String>,
+	registry: &BotRegistry,
+) -> Result<Args, String> {
+	let mut parsed = Args::default();
+	let mut args = args.into_iter();
+	let mut table_size = None;
+	let mut two_player = false;
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"play" => parsed.mode = Mode::Play,
+			"loop" => parsed.mode = Mode::Loop,
+			"tournament" => parsed.mode = Mode::Tournament,
+			"replay" => {
+				parsed.mode = Mode::Replay;
+				parsed.replay_file =
+					Some(args.next().ok_or(format!("{} needs a file", arg))?);
+			},
+			"-g" | "--games" => parsed.games = parse_value(&arg, args.next())?,
+			"-s" | "--seed" => parsed.seed = Some(parse_value(&arg, args.next())?),
+			"-t" | "--threads" => {
+				parsed.threads = Some(parse_value(&arg, args.next())?)
+			},
+			"-q" | "--quiet" => parsed.quiet = true,
+			"-f" | "--format" => {
+				parsed.format = match args.next().as_deref() {
+					Some("console") => Format::Console,
+					Some("jsonl") => Format::JsonLines,
+					Some(format) => {
+						return Err(format!("\"{}\" is not a valid format", format))
+					},
+					None => return Err(format!("{} needs a value", arg)),
+				}
+			},
+			"-p" | "--players" => table_size = Some(parse_value(&arg, args.next())?),
+			"-r" | "--reformation" => parsed.config.reformation.factions = true,
+			"-i" | "--inquisitor" => parsed.config.reformation.inquisitor = true,
+			"-2" | "--two-player" => two_player = true,
+			"-l" | "--list" => parsed.list = true,
+			"-h" | "--help" => parsed.help = true,
+			flag if flag.starts_with('-') => {
+				return Err(format!("Unknown option \"{}\"", flag))
+			},
+			name => match registry.get(name) {
+				Some(_) => parsed.bots.push(String::from(name)),
+				None => return Err(format!("Unknown bot \"{}\"", name)),
+			},
+		}
+	}
+
+	if parsed.bots.is_empty() {
+		parsed.bots = vec![String::from("MCTSBot"), String::from("HonestBot")];
+	}
+	if let Some(table_size) = table_size {
+		parsed.config.table_size = table_size;
+	}
+	// The variant only changes the deal so the other flags still apply
+	if two_player {
+		let variant = GameConfig::two_player();
+		if table_size.is_some_and(|size| size != variant.table_size) {
+			return Err(String::from("The two-player variant seats 2 players"));
+		}
+		parsed.config.first_player_coins = variant.first_player_coins;
+		parsed.config.dealt_cards = variant.dealt_cards;
+		parsed.config.table_size = variant.table_size;
+	}
+	if !(2..=10).contains(&parsed.config.table_size) {
+		return Err(String::from("A table seats between 2 and 10 players"));
+	}
+	if parsed.format == Format::JsonLines && parsed.mode == Mode::Tournament {
+		return Err(String::from(
+			"The jsonl format is only available in play and loop mode",
+		));
+	}
+
+	Ok(parsed)
+}
 
 fn main() {
-    let mut coup_game = Coup::new(vec![
-        Box::new(MctsBot),
-        Box::new(HonestBot)
-    ]);    
+	let registry = BotRegistry::default();
+	let args = match parse_args(env::args().skip(1), &registry) {
+		Ok(args) => args,
+		Err(error) => {
+			eprintln!("{}\n\n{}", error, USAGE);
+			process::exit(2);
+		},
+	};
+
+	if args.help {
+		println!("{}", USAGE);
+		return;
+	}
+	if args.list {
+		registry
+			.entries()
+			.iter()
+			.for_each(|entry| println!("{:<12} {}", entry.name, entry.description));
+		return;
+	}
+
+	if let Some(path) = &args.replay_file {
+		let replay = File::open(path)
+			.and_then(|file| Replay::from_reader(BufReader::new(file)))
+			.and_then(|replay| replay.run(io::stdin().lock(), io::stdout()));
+		if let Err(error) = replay {
+			eprintln!("Could not replay \"{}\": {}", path, error);
+			process::exit(1);
+		}
+		return;
+	}
+
+	let names = args.bots.iter().map(String::as_str).collect::<Vec<&str>>();
+
+	if args.mode == Mode::Tournament {
+		// The names were checked while parsing
+		let mut tournament = Tournament::from_registry(&registry, &names).unwrap();
+		if let Some(seed) = args.seed {
+			tournament = tournament.with_seed(seed);
+		}
+		if let Some(threads) = args.threads {
+			tournament = tournament.with_threads(threads);
+		}
+		tournament = tournament.with_config(args.config);
+
+		if args.quiet {
+			print_score(&tournament.run(args.games));
+		} else {
+			tournament.looping(args.games);
+		}
+		return;
+	}
+
+	let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+	let mut coup_game = Coup::new_from_registry(&registry, &names, seed).unwrap();
+	coup_game.set_config(args.config);
+
+	// The console rendering would mix with the log on stdout
+	coup_game.set_quiet(args.quiet || args.format == Format::JsonLines);
+	if args.format == Format::JsonLines {
+		coup_game.add_log_sink(Box::new(JsonLinesSink::new(io::stdout())));
+	}
+
+	match args.mode {
+		Mode::Loop => coup_game.looping(args.games),
+		_ => coup_game.play(),
+	}
+	if args.quiet && args.format == Format::Console {
+		print_score(coup_game.get_score());
+	}
+}
+
+// A plain score for scripts to read when the console rendering is off
+fn print_score(score: &Score) {
+	score.iter().for_each(|(name, score)| println!("{}\t{}", score, name));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use coup::Reformation;
 
-    // Play a single game
-    coup_game.play();
+	fn args(args: &str) -> Result<Args, String> {
+		parse_args(
+			args.split_whitespace().map(String::from),
+			&BotRegistry::default(),
+		)
+	}
 
-    // Or play multiple games
-    //coup_game.looping(50);
+	#[test]
+	fn test_parse_args() {
+		assert_eq!(
+			args(""),
+			Ok(Args {
+				bots: vec![String::from("MCTSBot"), String::from("HonestBot")],
+				..Args::default()
+			})
+		);
+		assert_eq!(
+			args("loop -g 50 --seed 42 -q staticbot HonestBot StaticBot"),
+			Ok(Args {
+				mode: Mode::Loop,
+				bots: vec![
+					String::from("staticbot"),
+					String::from("HonestBot"),
+					String::from("StaticBot")
+				],
+				games: 50,
+				seed: Some(42),
+				quiet: true,
+				..Args::default()
+			})
+		);
+		assert_eq!(
+			args("tournament -t 4 -f console DuelBot RandomBot").map(|a| a.threads),
+			Ok(Some(4))
+		);
+		assert_eq!(
+			args("-f jsonl HonestBot StaticBot").map(|a| a.format),
+			Ok(Format::JsonLines)
+		);
+		assert_eq!(
+			args("-r --inquisitor").map(|a| a.config.reformation),
+			Ok(Reformation::all())
+		);
+		assert_eq!(args("--players 8").map(|a| a.config.table_size), Ok(8));
+		assert_eq!(
+			args("-2 -p 2 --inquisitor -s 3").map(|a| a.config),
+			args("-s 3 -i --two-player").map(|a| a.config)
+		);
+		assert_eq!(
+			args("-i --two-player").map(|a| a.config),
+			Ok(GameConfig {
+				reformation: Reformation {
+					factions: false,
+					inquisitor: true
+				},
+				..GameConfig::two_player()
+			})
+		);
+		assert_eq!(
+			args("replay game.jsonl"),
+			Ok(Args {
+				mode: Mode::Replay,
+				replay_file: Some(String::from("game.jsonl")),
+				bots: vec![String::from("MCTSBot"), String::from("HonestBot")],
+				..Args::default()
+			})
+		);
+		assert!(args("-g many").is_err());
+		assert!(args("--seed").is_err());
+		assert!(args("replay").is_err());
+		assert!(args("-f xml").is_err());
+		assert!(args("--nope").is_err());
+		assert!(args("-p 11").is_err());
+		assert!(args("-p 4 -2").is_err());
+		assert!(args("-2 --players 4").is_err());
+		assert!(args("KateBot").is_err());
+		assert!(args("tournament -f jsonl").is_err());
+	}
 }
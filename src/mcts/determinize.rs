@@ -0,0 +1,230 @@
+// we can't see the other bots cards so we can't search the real game
+// instead every MCTS iteration deals the hidden cards again ("determinization")
+// and all iterations share one tree (information set MCTS)
+//
+// the hidden cards are everything that isn't in our hand or the discard pile
+// we don't deal them uniformly though: if a bot keeps claiming the duke it
+// probably has one, so claims in the history make a card more likely to be
+// dealt to that bot
+
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use super::sim_state::SimState;
+use crate::bot::Context;
+use crate::{Card, History};
+
+const CARDS: [Card; 5] = [Card::Duke, Card::Assassin, Card::Captain, Card::Contessa, Card::Ambassador];
+
+// how much more likely a card gets per claim
+const CLAIM_WEIGHT: f64 = 4.0;
+
+fn card_index(card: Card) -> usize {
+    match card {
+        // the inquisitor takes the place of the ambassador
+        Card::Inquisitor => CARDS.iter().position(|c| *c == Card::Ambassador).unwrap(),
+        card => CARDS.iter().position(|c| *c == card).unwrap(),
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Determinizer {
+    // per bot name how often they claimed each card (indexed like CARDS)
+    claims: HashMap<String, [u32; 5]>,
+}
+
+impl Determinizer {
+    // go through the history and count what everyone claimed
+    pub fn from_context(context: &Context) -> Self {
+        let mut claims: HashMap<String, [u32; 5]> = HashMap::new();
+        // the last claim so we can take it back if it turns out to be a bluff
+        let mut last_claim: Option<(String, Vec<Card>)> = None;
+        // the ambassador or the inquisitor, whichever swaps and blocks stealing
+        let exchange = context.config.reformation.exchange_card();
+
+        for event in context.history.iter() {
+            let claim = match event {
+                History::ActionTax { by } => Some((by, vec![Card::Duke])),
+                History::ActionAssassination { by, .. } => Some((by, vec![Card::Assassin])),
+                History::ActionStealing { by, .. } => Some((by, vec![Card::Captain])),
+                History::ActionSwapping { by } => Some((by, vec![exchange])),
+                History::CounterForeignAid { by, .. } => Some((by, vec![Card::Duke])),
+                History::CounterAssassination { by, .. } => Some((by, vec![Card::Contessa])),
+                History::CounterStealing { by, .. } => Some((by, vec![Card::Captain, exchange])),
+                _ => None,
+            };
+
+            if let Some((by, cards)) = claim {
+                let counts = claims.entry(by.clone()).or_default();
+                for card in cards.iter() {
+                    counts[card_index(*card)] += 1;
+                }
+                last_claim = Some((by.clone(), cards));
+                continue;
+            }
+
+            match event {
+                // caught bluffing, so whatever they claimed they didn't have
+                History::ChallengeSuccessful { target, .. } | History::CounterChallengeSuccessful { target, .. } => {
+                    if let Some((by, cards)) = &last_claim {
+                        if by == target {
+                            let counts = claims.entry(by.clone()).or_default();
+                            for card in cards.iter() {
+                                counts[card_index(*card)] = 0;
+                            }
+                        }
+                    }
+                }
+                // the card was shown and shuffled back into the deck
+                History::RevealCard { by, card } => {
+                    claims.entry(by.clone()).or_default()[card_index(*card)] = 0;
+                }
+                // a new hand, nothing we knew is true anymore
+                History::ExchangeCards { by } => {
+                    claims.insert(by.clone(), [0; 5]);
+                }
+                _ => {}
+            }
+        }
+
+        Self { claims }
+    }
+
+    fn weight(&self, name: &str, card: Card) -> f64 {
+        let claims = self.claims.get(name).map_or(0, |c| c[card_index(card)]);
+        1.0 + CLAIM_WEIGHT * claims as f64
+    }
+
+    // deal all hidden cards of the state again
+    // our own hand, the discard pile, coins and how many cards everyone has stay the same
+    pub fn sample<R: Rng + ?Sized>(&self, state: &SimState, rng: &mut R) -> SimState {
+        let mut next = state.clone();
+
+        let mut pool = next.deck.clone();
+        for (index, player) in next.players.iter().enumerate() {
+            if index != next.me {
+                pool.extend(player.cards.iter().copied());
+            }
+        }
+        pool.shuffle(rng);
+
+        for index in 0..next.players.len() {
+            if index == next.me {
+                continue;
+            }
+
+            let count = next.players[index].cards.len();
+            let mut hand = Vec::with_capacity(count);
+            for _ in 0..count {
+                let name = &next.players[index].name;
+                let pick = pool
+                    .choose_weighted(rng, |card| self.weight(name, *card))
+                    .ok()
+                    .and_then(|card| pool.iter().position(|c| c == card));
+                if let Some(pick) = pick {
+                    hand.push(pool.swap_remove(pick));
+                }
+            }
+            next.players[index].cards = hand;
+        }
+
+        pool.shuffle(rng);
+        next.deck = pool;
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcts::sim_state::{Phase, SimPlayer};
+
+    fn context(history: Vec<History>) -> Context {
+        Context {
+            name: "me".to_string(),
+            cards: vec![Card::Captain, Card::Captain],
+            coins: 2,
+            playing_bots: vec![],
+            discard_pile: vec![],
+            history,
+            moves: 0,
+            score: vec![],
+            treasury_reserve: 0,
+            copies: 3,
+            treasury: 50,
+            config: Default::default(),
+        }
+    }
+
+    fn state() -> SimState {
+        let player = |name: &str, cards: Vec<Card>| SimPlayer { name: name.to_string(), cards, coins: 2, allegiance: None };
+        SimState {
+            me: 0,
+            players: vec![
+                player("me", vec![Card::Captain, Card::Captain]),
+                player("a", vec![Card::Contessa, Card::Ambassador]),
+                player("b", vec![Card::Duke]),
+            ],
+            turn: 0,
+            deck: vec![Card::Duke, Card::Duke, Card::Assassin, Card::Contessa],
+            discard_pile: vec![Card::Assassin],
+            moves: 0,
+            phase: Phase::Turn,
+            copies: 3,
+            treasury: 50,
+            treasury_reserve: 0,
+            config: Default::default(),
+        }
+    }
+
+    fn count(cards: &[Card], card: Card) -> usize {
+        cards.iter().filter(|c| **c == card).count()
+    }
+
+    #[test]
+    fn test_sample_keeps_cards() {
+        let determinizer = Determinizer::default();
+        let before = state();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..50 {
+            let after = determinizer.sample(&before, &mut rng);
+            assert_eq!(after.players[0].cards, before.players[0].cards);
+            assert_eq!(after.players[1].cards.len(), 2);
+            assert_eq!(after.players[2].cards.len(), 1);
+            assert_eq!(after.deck.len(), before.deck.len());
+            assert_eq!(after.discard_pile, before.discard_pile);
+
+            let mut all = after.deck.clone();
+            after.players.iter().for_each(|p| all.extend(p.cards.iter().copied()));
+            for card in CARDS {
+                assert_eq!(count(&all, card), count(&before.deck, card) + before.players.iter().map(|p| count(&p.cards, card)).sum::<usize>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_claims() {
+        let tax = |by: &str| History::ActionTax { by: by.to_string() };
+        let determinizer = Determinizer::from_context(&context(vec![
+            tax("a"),
+            tax("a"),
+            tax("a"),
+            tax("b"),
+            History::ChallengeSuccessful { by: "me".to_string(), target: "b".to_string() },
+        ]));
+        assert!(determinizer.weight("a", Card::Duke) > determinizer.weight("a", Card::Contessa));
+        // b got caught bluffing
+        assert_eq!(determinizer.weight("b", Card::Duke), 1.0);
+
+        // a keeps claiming the duke so should end up with one most of the time
+        let mut rng = StdRng::seed_from_u64(2);
+        let with_duke = (0..200)
+            .filter(|_| determinizer.sample(&state(), &mut rng).players[1].cards.contains(&Card::Duke))
+            .count();
+        let without = (0..200)
+            .filter(|_| Determinizer::default().sample(&state(), &mut rng).players[1].cards.contains(&Card::Duke))
+            .count();
+        assert!(with_duke > without);
+    }
+}
@@ -1,2 +1,4 @@
+pub mod determinize;
+pub mod rollout;
 pub mod sim_state;
 pub mod tree;
@@ -0,0 +1,288 @@
+// rollout policies decide the moves in a rollout (after we left the tree)
+// random rollouts are really noisy in coup (everyone bluffs and challenges
+// all the time) so we have a few smarter ones to pick from:
+//   RandomPolicy    - every decision at random
+//   HonestPolicy    - random actions, honest challenges and counters
+//   HeuristicPolicy - acts on the cards it holds, honest challenges and counters
+//   BotPolicy       - asks a real bot (HonestBot, DuelBot, ...) what it would do
+//
+// a rollout can run on any thread so MctsConfig keeps a factory that builds
+// a new policy for every search, just like BotFactory does for bots
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rand::prelude::*;
+use super::sim_state::{card_rank, Move, Phase, SimState};
+use crate::bot::{BotFactory, BotInterface, Context, OtherBot};
+use crate::{Action, Card};
+
+pub trait RolloutPolicy {
+    // pick the move for whoever has to move in `state` (see SimState::to_move)
+    fn choose(&mut self, state: &SimState, rng: &mut dyn RngCore) -> Move;
+}
+
+pub type RolloutPolicyFactory = Arc<dyn Fn() -> Box<dyn RolloutPolicy> + Send + Sync>;
+
+// wrap a closure that builds a policy into a RolloutPolicyFactory
+// e.g. policy(|| HeuristicPolicy) or policy(|| BotPolicy::new(factory(|| HonestBot)))
+pub fn policy<P, F>(make: F) -> RolloutPolicyFactory
+where
+    P: RolloutPolicy + 'static,
+    F: Fn() -> P + Send + Sync + 'static,
+{
+    Arc::new(move || Box::new(make()) as Box<dyn RolloutPolicy>)
+}
+
+pub struct RandomPolicy;
+
+impl RolloutPolicy for RandomPolicy {
+    fn choose(&mut self, state: &SimState, rng: &mut dyn RngCore) -> Move {
+        match state.legal_moves().choose(rng) {
+            Some(mv) => mv.clone(),
+            None => state.default_move(rng),
+        }
+    }
+}
+
+pub struct HonestPolicy;
+
+impl RolloutPolicy for HonestPolicy {
+    fn choose(&mut self, state: &SimState, rng: &mut dyn RngCore) -> Move {
+        state.default_move(rng)
+    }
+}
+
+// never bluffs: coups when it can, otherwise uses the best card it holds
+// and picks on whoever is doing best
+pub struct HeuristicPolicy;
+
+impl RolloutPolicy for HeuristicPolicy {
+    fn choose(&mut self, state: &SimState, rng: &mut dyn RngCore) -> Move {
+        if state.phase != Phase::Turn {
+            return state.default_move(rng);
+        }
+
+        let me = &state.players[state.turn];
+        let Some(target) = state
+            .others_in_order(state.turn)
+            .into_iter()
+            .filter(|index| state.can_target(state.turn, *index))
+            .map(|index| &state.players[index])
+            .max_by_key(|player| (player.cards.len(), player.coins))
+        else {
+            return state.default_move(rng);
+        };
+        let name = target.name.clone();
+
+        let action = if me.coins >= state.config.coup_cost {
+            Action::Coup(name)
+        } else if me.cards.contains(&Card::Assassin) && me.coins >= state.config.assassination_cost {
+            Action::Assassination(name)
+        } else if me.cards.contains(&Card::Duke) {
+            Action::Tax
+        } else if me.cards.contains(&Card::Captain) && target.coins > 0 {
+            Action::Stealing(name)
+        } else if me.cards.contains(&state.config.reformation.exchange_card()) {
+            Action::Swapping
+        } else {
+            Action::ForeignAid
+        };
+        Move::Act(action)
+    }
+}
+
+// lets a bot play every seat in the rollout
+// each seat gets its own instance so bots that remember things don't mix
+// up players, anything a bot answers that isn't allowed falls back to the
+// honest default
+pub struct BotPolicy {
+    factory: BotFactory,
+    bots: HashMap<usize, Box<dyn BotInterface>>,
+}
+
+impl BotPolicy {
+    pub fn new(factory: BotFactory) -> Self {
+        Self { factory, bots: HashMap::new() }
+    }
+
+    // what the player would see in the real game
+    // the sim doesn't keep a history so that's empty
+    fn context(state: &SimState, player: usize, cards: Vec<Card>) -> Context {
+        Context {
+            name: state.players[player].name.clone(),
+            cards,
+            coins: state.players[player].coins,
+            playing_bots: state
+                .players
+                .iter()
+                .filter(|p| p.is_alive())
+                .map(|p| OtherBot {
+                    name: p.name.clone(),
+                    coins: p.coins,
+                    cards: p.cards.len() as u8,
+                    allegiance: p.allegiance,
+                })
+                .collect(),
+            discard_pile: state.discard_pile.clone(),
+            history: vec![],
+            moves: state.moves + 1,
+            score: vec![],
+            config: state.config,
+            treasury: state.treasury,
+            treasury_reserve: state.treasury_reserve,
+            copies: state.copies,
+        }
+    }
+
+    fn is_valid(state: &SimState, action: &Action) -> bool {
+        let me = &state.players[state.turn];
+        let target_ok = |name: &String| state.player_index(name).is_some_and(|index| state.can_target(state.turn, index));
+        let factions = state.config.reformation.factions;
+
+        match action {
+            Action::Coup(name) => me.coins >= state.config.coup_cost && target_ok(name),
+            _ if me.coins >= state.config.auto_coup_coins => false,
+            Action::Assassination(name) => me.coins >= state.config.assassination_cost && target_ok(name),
+            Action::Stealing(name) => target_ok(name),
+            Action::Income | Action::ForeignAid | Action::Tax => true,
+            // we need two cards in the deck to swap, one with the inquisitor
+            Action::Swapping if state.config.reformation.inquisitor => !state.deck.is_empty(),
+            Action::Swapping => state.deck.len() >= 2,
+            Action::Conversion(name) => {
+                let cost = if *name == me.name { 1 } else { 2 };
+                factions && me.coins >= cost && state.player_index(name).is_some_and(|i| state.players[i].is_alive())
+            }
+            Action::Embezzlement => factions,
+            // the sim doesn't play examining
+            Action::Examine(_) => false,
+        }
+    }
+
+    fn ask(bot: &mut dyn BotInterface, state: &SimState, player: usize) -> Option<Move> {
+        let name = |index: usize| state.players[index].name.clone();
+        let cards = state.players[player].cards.clone();
+
+        match &state.phase {
+            Phase::Turn => {
+                let context = Self::context(state, player, cards);
+                let action = if state.players[player].coins >= state.config.auto_coup_coins {
+                    Action::Coup(bot.on_auto_coup(&context))
+                } else {
+                    bot.on_turn(&context)
+                };
+                Self::is_valid(state, &action).then_some(Move::Act(action))
+            }
+            Phase::ChallengeAction { action, .. } => {
+                let context = Self::context(state, player, cards);
+                let challenge = bot.on_challenge_action_round(action, name(state.turn), &context);
+                Some(if challenge { Move::Challenge } else { Move::Pass })
+            }
+            Phase::Counter { action, .. } => {
+                let context = Self::context(state, player, cards);
+                let counter = bot.on_counter(action, name(state.turn), &context);
+                Some(if counter { Move::Counter } else { Move::Pass })
+            }
+            Phase::ChallengeCounter { action, blocker, .. } => {
+                let context = Self::context(state, player, cards);
+                let challenge = bot.on_challenge_counter_round(action, name(*blocker), &context);
+                Some(if challenge { Move::Challenge } else { Move::Pass })
+            }
+            // some bots (HonestBot, DuelBot) expect two cards in hand when
+            // swapping so with one card left we don't ask
+            Phase::Swap if cards.len() < 4 => None,
+            Phase::Swap => {
+                // the two cards drawn are the last two in the hand
+                let mut hand = cards;
+                let drawn = hand.split_off(hand.len().saturating_sub(2));
+                let new_cards = [*drawn.first()?, *drawn.get(1)?];
+                let context = Self::context(state, player, hand);
+                let mut back = bot.on_swapping_cards(new_cards, &context);
+                back.sort_by_key(|card| card_rank(*card));
+                state.legal_moves().into_iter().find(|mv| *mv == Move::Return(back))
+            }
+            Phase::Exchange => {
+                // the card drawn is the last one in the hand
+                let mut hand = cards;
+                let new_card = hand.pop()?;
+                let context = Self::context(state, player, hand.clone());
+                let back = bot.on_exchanging_card(new_card, &context);
+                (back == new_card || hand.contains(&back)).then_some(Move::ReturnOne(back))
+            }
+            Phase::LoseCard { .. } => {
+                let context = Self::context(state, player, cards.clone());
+                let card = bot.on_card_loss(&context);
+                cards.contains(&card).then_some(Move::Lose(card))
+            }
+            Phase::AfterChallenge { .. } | Phase::Perform { .. } | Phase::EndTurn => None,
+        }
+    }
+}
+
+impl RolloutPolicy for BotPolicy {
+    fn choose(&mut self, state: &SimState, rng: &mut dyn RngCore) -> Move {
+        let player = state.to_move();
+        let factory = &self.factory;
+        let bot = self.bots.entry(player).or_insert_with(|| factory());
+
+        match Self::ask(bot.as_mut(), state, player) {
+            Some(mv) => mv,
+            None => state.default_move(rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::factory;
+    use crate::bots::HonestBot;
+    use crate::mcts::sim_state::SimPlayer;
+
+    fn state() -> SimState {
+        let player = |name: &str, cards: Vec<Card>, coins: u8| SimPlayer { name: name.to_string(), cards, coins, allegiance: None };
+        SimState {
+            me: 0,
+            players: vec![
+                player("me", vec![Card::Assassin, Card::Captain], 3),
+                player("a", vec![Card::Contessa, Card::Ambassador], 2),
+                player("b", vec![Card::Duke], 0),
+            ],
+            turn: 0,
+            deck: vec![Card::Duke, Card::Duke, Card::Captain],
+            discard_pile: vec![],
+            moves: 0,
+            phase: Phase::Turn,
+            copies: 3,
+            treasury: 50,
+            treasury_reserve: 0,
+            config: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_policies() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let sim = state();
+
+        let mv = HeuristicPolicy.choose(&sim, &mut rng);
+        assert_eq!(mv, Move::Act(Action::Assassination("a".to_string())));
+        assert!(sim.legal_moves().contains(&RandomPolicy.choose(&sim, &mut rng)));
+
+        // HonestBot assassinates the bot with the least cards and a blocks it
+        // with the contessa it really has
+        let mut honest = BotPolicy::new(factory(|| HonestBot));
+        let mv = honest.choose(&sim, &mut rng);
+        assert_eq!(mv, Move::Act(Action::Assassination("b".to_string())));
+        let mut next = sim.apply_move(&Move::Act(Action::Assassination("a".to_string())), &mut rng);
+        while next.phase != Phase::Turn {
+            let mv = honest.choose(&next, &mut rng);
+            if next.to_move() == 1 && matches!(next.phase, Phase::Counter { .. }) {
+                assert_eq!(mv, Move::Counter);
+            }
+            next = next.apply_move(&mv, &mut rng);
+        }
+        assert_eq!(next.players[1].cards.len(), 2);
+        assert_eq!(honest.bots.len(), 3);
+    }
+}
@@ -1,64 +1,972 @@
-// this is a simplified version of the game state used ONLY for simulation
-// we’re not modeling full coup yet, just enough to simulate decisions
+// this is the game state we run our simulations on
+// it models the whole game: every action against every target, turn order,
+// everyones coins and cards, challenges, counters, card loss and elimination
+// a turn is split into phases so every choice a bot gets asked about in the
+// real game (challenge, counter, counter challenge, swap, card loss) is its
+// own decision the search can look at
+// we don't know the other bots cards so from_context deals them at random
+// from the cards we haven't seen yet
+// with the reformation rules it knows the factions, conversion and
+// embezzlement too, examining only gets as far as the challenge
 
-use crate::{Action, Card};
-use crate::bot::{Context, OtherBot};
+use rand::prelude::*;
+use serde::Serialize;
+use crate::{Action, Allegiance, Card, GameConfig, Reformation};
+use crate::bot::Context;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimPlayer {
+    pub name: String,
+    pub cards: Vec<Card>,
+    pub coins: u8,
+    // the faction with the reformation rules, None in the base game
+    pub allegiance: Option<Allegiance>,
+}
+
+impl SimPlayer {
+    pub fn is_alive(&self) -> bool {
+        !self.cards.is_empty()
+    }
+}
+
+// one decision by one player
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum Move {
+    // the player whose turn it is takes an action
+    Act(Action),
+    Challenge,
+    Counter,
+    // don't challenge / don't counter
+    Pass,
+    // the two cards that go back into the deck after swapping
+    Return([Card; 2]),
+    // the card that goes back into the deck after exchanging with the inquisitor
+    ReturnOne(Card),
+    Lose(Card),
+}
+
+// where in the turn we are
+// the actor is always the player whose turn it is
+#[derive(Clone, Debug, PartialEq)]
+pub enum Phase {
+    // waiting for the turn player to pick an action
+    Turn,
+    // everyone in pending (in order) gets to challenge the action
+    ChallengeAction { action: Action, pending: Vec<usize> },
+    // everyone in pending gets to block the action
+    Counter { action: Action, pending: Vec<usize> },
+    // everyone in pending gets to challenge the block
+    ChallengeCounter { action: Action, blocker: usize, pending: Vec<usize> },
+    // the actor has drawn two cards and has to give two back
+    Swap,
+    // the actor has drawn one card with the inquisitor and has to give one back
+    Exchange,
+    // player picks a card to lose, then we go on with `then`
+    LoseCard { player: usize, then: Box<Phase> },
+    // the phases below need no decision and are skipped over right away
+    AfterChallenge { action: Action },
+    Perform { action: Action },
+    EndTurn,
+}
+
+#[derive(Clone, Debug)]
 pub struct SimState {
-    pub my_name: String,
-    pub my_cards: Vec<Card>,
-    pub my_coins: u8,
-    pub opponents: Vec<OtherBot>,
+    // index of our own bot in players
+    pub me: usize,
+    // everyone in turn order, dead players stay in here so indices never change
+    pub players: Vec<SimPlayer>,
+    // index of the player whose turn it is
+    pub turn: usize,
+    pub deck: Vec<Card>,
+    pub discard_pile: Vec<Card>,
+    pub moves: usize,
+    pub phase: Phase,
+    // how many copies of each card there are, 5 at big tables
+    pub copies: usize,
+    // coins left to pay out, income, foreign aid and tax only get what's left
+    pub treasury: u8,
+    // coins paid for conversions waiting for someone to embezzle them
+    pub treasury_reserve: u8,
+    // the costs and amounts of the real game, games longer than max_moves are
+    // called a draw just like the engine does (moves counts from the start of
+    // the real game)
+    pub config: GameConfig,
+}
+
+// which card you need to claim to take an action
+// with the inquisitor rules the inquisitor swaps instead of the ambassador
+pub fn card_for_action(action: &Action, reformation: &Reformation) -> Option<Card> {
+    match action {
+        Action::Assassination(_) => Some(Card::Assassin),
+        Action::Swapping => Some(reformation.exchange_card()),
+        Action::Stealing(_) => Some(Card::Captain),
+        Action::Tax => Some(Card::Duke),
+        Action::Examine(_) => Some(Card::Inquisitor),
+        Action::Coup(_) | Action::Income | Action::ForeignAid | Action::Conversion(_) | Action::Embezzlement => None,
+    }
+}
+
+// which cards can block an action
+pub fn counters_for_action(action: &Action, reformation: &Reformation) -> &'static [Card] {
+    match action {
+        Action::Assassination(_) => &[Card::Contessa],
+        Action::ForeignAid => &[Card::Duke],
+        Action::Stealing(_) if reformation.inquisitor => &[Card::Captain, Card::Inquisitor],
+        Action::Stealing(_) => &[Card::Captain, Card::Ambassador],
+        _ => &[],
+    }
+}
+
+// rough value of a card, used when a player has to pick what to keep
+pub fn card_rank(card: Card) -> u8 {
+    match card {
+        Card::Duke => 5,
+        Card::Assassin => 4,
+        Card::Captain => 3,
+        Card::Contessa => 2,
+        Card::Ambassador | Card::Inquisitor => 1,
+    }
 }
 
 impl SimState {
-    pub fn from_context(context: &Context) -> Self {
+    // build the sim from what the engine tells us
+    // hidden cards (other hands + deck) are dealt at random from the cards
+    // that aren't in our hand or the discard pile
+    pub fn from_context<R: Rng + ?Sized>(context: &Context, rng: &mut R) -> Self {
+        let mut unseen = context
+            .config
+            .reformation
+            .cards()
+            .iter()
+            .flat_map(|card| vec![*card; context.copies])
+            .collect::<Vec<Card>>();
+        unseen.shuffle(rng);
+        for card in context.cards.iter().chain(context.discard_pile.iter()) {
+            if let Some(index) = unseen.iter().position(|c| c == card) {
+                unseen.remove(index);
+            }
+        }
+
+        let players = context
+            .playing_bots
+            .iter()
+            .map(|bot| SimPlayer {
+                name: bot.name.clone(),
+                cards: if bot.name == context.name {
+                    context.cards.clone()
+                } else {
+                    (0..bot.cards).filter_map(|_| unseen.pop()).collect()
+                },
+                coins: if bot.name == context.name { context.coins } else { bot.coins },
+                allegiance: bot.allegiance,
+            })
+            .collect::<Vec<SimPlayer>>();
+
+        let me = players.iter().position(|p| p.name == context.name).unwrap_or(0);
+
         Self {
-            my_name: context.name.clone(),
-            my_cards: context.cards.clone(),
-            my_coins: context.coins,
-            opponents: context.playing_bots.clone(),
+            me,
+            players,
+            turn: me,
+            deck: unseen,
+            discard_pile: context.discard_pile.clone(),
+            // the engine already counts the move we're in
+            moves: context.moves.saturating_sub(1),
+            phase: Phase::Turn,
+            copies: context.copies,
+            treasury: context.treasury,
+            treasury_reserve: context.treasury_reserve,
+            config: context.config,
         }
     }
-    // return possible actions from current state
-    // rn we keep it simple (income + coup when possible)
+
+    pub fn player_index(&self, name: &str) -> Option<usize> {
+        self.players.iter().position(|p| p.name == name)
+    }
+
+    fn alive_count(&self) -> usize {
+        self.players.iter().filter(|p| p.is_alive()).count()
+    }
+
+    // everyone still in the game after `from` in turn order (not including `from`)
+    pub fn others_in_order(&self, from: usize) -> Vec<usize> {
+        let n = self.players.len();
+        (1..n)
+            .map(|offset| (from + offset) % n)
+            .filter(|index| self.players[*index].is_alive())
+            .collect()
+    }
+
+    // with factions you can't coup, assassinate or steal from your own side
+    // unless everyone is on it, the same rule as Context::can_target
+    pub fn can_target(&self, from: usize, target: usize) -> bool {
+        let mine = self.players[from].allegiance;
+        target != from
+            && self.players[target].is_alive()
+            && (mine.is_none()
+                || self.players[target].allegiance != mine
+                || self.players.iter().filter(|p| p.is_alive()).all(|p| p.allegiance == mine))
+    }
+
+    // converting yourself is cheaper than converting someone else
+    fn conversion_cost(&self, from: usize, target: usize) -> u8 {
+        if from == target { 1 } else { 2 }
+    }
+
+    // all actions the player whose turn it is can take
     pub fn legal_actions(&self) -> Vec<Action> {
-        let mut actions = vec![Action::Income];
+        if self.is_terminal() {
+            return vec![];
+        }
+
+        let player = &self.players[self.turn];
+        let targets = self
+            .others_in_order(self.turn)
+            .into_iter()
+            .filter(|index| self.can_target(self.turn, *index))
+            .map(|index| &self.players[index])
+            .collect::<Vec<&SimPlayer>>();
 
-        if self.my_coins >= 7 {
-            if let Some(target) = self.opponents.first() {
+        // 10 coins or more means you have to coup
+        if player.coins >= self.config.auto_coup_coins {
+            return targets.iter().map(|t| Action::Coup(t.name.clone())).collect();
+        }
+
+        let mut actions = vec![Action::Income, Action::ForeignAid, Action::Tax, Action::Swapping];
+        for target in targets.iter() {
+            // stealing from someone with no coins does nothing
+            if target.coins > 0 {
+                actions.push(Action::Stealing(target.name.clone()));
+            }
+            if player.coins >= self.config.assassination_cost {
+                actions.push(Action::Assassination(target.name.clone()));
+            }
+            if player.coins >= self.config.coup_cost {
                 actions.push(Action::Coup(target.name.clone()));
             }
         }
 
+        if self.config.reformation.factions {
+            if self.treasury_reserve > 0 {
+                actions.push(Action::Embezzlement);
+            }
+            // anyone can be converted, us included
+            for index in std::iter::once(self.turn).chain(self.others_in_order(self.turn)) {
+                if player.coins >= self.conversion_cost(self.turn, index) {
+                    actions.push(Action::Conversion(self.players[index].name.clone()));
+                }
+            }
+        }
+
         actions
     }
-    // simulate what happens after taking an action
-    // this is VERY simplified and does not model full coup logic yet
 
-    pub fn apply_action(&self, action: &Action) -> Self {
+    // who has to make the next decision
+    pub fn to_move(&self) -> usize {
+        match &self.phase {
+            Phase::ChallengeAction { pending, .. }
+            | Phase::Counter { pending, .. }
+            | Phase::ChallengeCounter { pending, .. } => pending.first().copied().unwrap_or(self.turn),
+            Phase::LoseCard { player, .. } => *player,
+            _ => self.turn,
+        }
+    }
+
+    // all moves the player in to_move can make right now
+    pub fn legal_moves(&self) -> Vec<Move> {
+        if self.is_terminal() {
+            return vec![];
+        }
+
+        match &self.phase {
+            Phase::Turn => self.legal_actions().into_iter().map(Move::Act).collect(),
+            Phase::ChallengeAction { .. } | Phase::ChallengeCounter { .. } => vec![Move::Pass, Move::Challenge],
+            Phase::Counter { .. } => vec![Move::Pass, Move::Counter],
+            Phase::Swap => {
+                // every pair of cards we could put back (no duplicates)
+                let hand = &self.players[self.turn].cards;
+                let mut moves = vec![];
+                for i in 0..hand.len() {
+                    for j in (i + 1)..hand.len() {
+                        let mut pair = [hand[i], hand[j]];
+                        pair.sort_by_key(|card| card_rank(*card));
+                        if !moves.contains(&Move::Return(pair)) {
+                            moves.push(Move::Return(pair));
+                        }
+                    }
+                }
+                moves
+            }
+            Phase::Exchange => {
+                let mut moves = vec![];
+                for card in self.players[self.turn].cards.iter() {
+                    if !moves.contains(&Move::ReturnOne(*card)) {
+                        moves.push(Move::ReturnOne(*card));
+                    }
+                }
+                moves
+            }
+            Phase::LoseCard { player, .. } => {
+                let mut moves = vec![];
+                for card in self.players[*player].cards.iter() {
+                    if !moves.contains(&Move::Lose(*card)) {
+                        moves.push(Move::Lose(*card));
+                    }
+                }
+                moves
+            }
+            Phase::AfterChallenge { .. } | Phase::Perform { .. } | Phase::EndTurn => vec![],
+        }
+    }
+
+    // the turn player takes an action and everyone else reacts honestly
+    // (see default_move) until it's the next players turn
+    pub fn apply_action<R: Rng + ?Sized>(&self, action: &Action, rng: &mut R) -> Self {
+        let mut next = self.apply_move(&Move::Act(action.clone()), rng);
+        while next.phase != Phase::Turn && !next.is_terminal() {
+            let reaction = next.default_move(rng);
+            next = next.apply_move(&reaction, rng);
+        }
+        next
+    }
+
+    pub fn apply_move<R: Rng + ?Sized>(&self, mv: &Move, rng: &mut R) -> Self {
         let mut next = self.clone();
+        next.play_move(mv, rng);
+        next.settle();
+        next
+    }
 
+    // what a simple honest player would do, we use this for the other bots
+    // outside of the tree: a random action on their turn, counter when they
+    // hold the card and only challenge when they can see all copies
+    pub fn default_move<R: Rng + ?Sized>(&self, rng: &mut R) -> Move {
+        let player = self.to_move();
+        match &self.phase {
+            Phase::Turn => match self.legal_actions().choose(rng) {
+                Some(action) => Move::Act(action.clone()),
+                None => Move::Act(Action::Income),
+            },
+            Phase::ChallengeAction { action, .. } => match card_for_action(action, &self.config.reformation) {
+                Some(card) if self.sees_all(player, &[card]) => Move::Challenge,
+                _ => Move::Pass,
+            },
+            Phase::Counter { action, .. } => {
+                let counters = counters_for_action(action, &self.config.reformation);
+                if counters.iter().any(|card| self.players[player].cards.contains(card)) {
+                    Move::Counter
+                } else {
+                    Move::Pass
+                }
+            }
+            Phase::ChallengeCounter { action, .. } => {
+                if self.sees_all(player, counters_for_action(action, &self.config.reformation)) {
+                    Move::Challenge
+                } else {
+                    Move::Pass
+                }
+            }
+            Phase::Swap => {
+                let mut hand = self.players[self.turn].cards.clone();
+                hand.sort_by_key(|card| card_rank(*card));
+                Move::Return([hand[0], hand[1]])
+            }
+            Phase::Exchange => {
+                let cards = &self.players[self.turn].cards;
+                Move::ReturnOne(*cards.iter().min_by_key(|card| card_rank(**card)).unwrap())
+            }
+            Phase::LoseCard { player, .. } => {
+                let cards = &self.players[*player].cards;
+                Move::Lose(*cards.iter().min_by_key(|card| card_rank(**card)).unwrap())
+            }
+            Phase::AfterChallenge { .. } | Phase::Perform { .. } | Phase::EndTurn => Move::Pass,
+        }
+    }
+
+    // true if `player` can see all copies of every one of these cards
+    // (then whoever claims one of them must be bluffing)
+    fn sees_all(&self, player: usize, cards: &[Card]) -> bool {
+        cards.iter().all(|card| {
+            let visible = self.players[player].cards.iter().filter(|c| *c == card).count()
+                + self.discard_pile.iter().filter(|c| *c == card).count();
+            visible == self.copies
+        })
+    }
+
+    fn target_of(&self, action: &Action) -> Option<usize> {
         match action {
-            Action::Income => next.my_coins += 1,
-            Action::Coup(_) => next.my_coins -= 7,
-            _ => {}
+            Action::Assassination(name)
+            | Action::Coup(name)
+            | Action::Stealing(name)
+            | Action::Conversion(name) => self.player_index(name),
+            _ => None,
         }
+    }
 
-        next
+    fn play_move<R: Rng + ?Sized>(&mut self, mv: &Move, rng: &mut R) {
+        let actor = self.turn;
+        let phase = std::mem::replace(&mut self.phase, Phase::EndTurn);
+
+        self.phase = match (phase, mv) {
+            (Phase::Turn, Move::Act(action)) => match action {
+                // coup can't be challenged or blocked
+                Action::Coup(_) => {
+                    self.pay(actor, self.config.coup_cost);
+                    match self.target_of(action) {
+                        Some(target) => Phase::LoseCard { player: target, then: Box::new(Phase::EndTurn) },
+                        None => Phase::EndTurn,
+                    }
+                }
+                // embezzling is a claim not to have the duke so it can be challenged too
+                _ if card_for_action(action, &self.config.reformation).is_some()
+                    || *action == Action::Embezzlement =>
+                {
+                    Phase::ChallengeAction { action: action.clone(), pending: self.others_in_order(actor) }
+                }
+                _ => Phase::AfterChallenge { action: action.clone() },
+            },
+
+            (Phase::ChallengeAction { action, mut pending }, Move::Pass) => {
+                pending.remove(0);
+                Phase::ChallengeAction { action, pending }
+            }
+            (Phase::ChallengeAction { action: Action::Embezzlement, pending }, Move::Challenge) => {
+                let challenger = pending[0];
+                if self.players[actor].cards.contains(&Card::Duke) {
+                    // caught with the duke, no coins for them
+                    Phase::LoseCard { player: actor, then: Box::new(Phase::EndTurn) }
+                } else {
+                    // they showed their whole hand so it goes back and they draw a new one
+                    let hand = std::mem::take(&mut self.players[actor].cards);
+                    let count = hand.len();
+                    self.deck.extend(hand);
+                    self.deck.shuffle(rng);
+                    let new_hand = (0..count).filter_map(|_| self.deck.pop()).collect();
+                    self.players[actor].cards = new_hand;
+                    let then = Phase::AfterChallenge { action: Action::Embezzlement };
+                    Phase::LoseCard { player: challenger, then: Box::new(then) }
+                }
+            }
+            (Phase::ChallengeAction { action, pending }, Move::Challenge) => {
+                let challenger = pending[0];
+                let card = card_for_action(&action, &self.config.reformation).unwrap();
+                if let Some(index) = self.players[actor].cards.iter().position(|c| *c == card) {
+                    // show the card, shuffle it back and draw a new one
+                    self.players[actor].cards.remove(index);
+                    self.deck.push(card);
+                    self.deck.shuffle(rng);
+                    if let Some(new_card) = self.deck.pop() {
+                        self.players[actor].cards.push(new_card);
+                    }
+                    Phase::LoseCard { player: challenger, then: Box::new(Phase::AfterChallenge { action }) }
+                } else {
+                    // caught bluffing, the action doesn't happen
+                    Phase::LoseCard { player: actor, then: Box::new(Phase::EndTurn) }
+                }
+            }
+
+            (Phase::Counter { action, mut pending }, Move::Pass) => {
+                pending.remove(0);
+                Phase::Counter { action, pending }
+            }
+            (Phase::Counter { action, pending }, Move::Counter) => {
+                let blocker = pending[0];
+                Phase::ChallengeCounter { action, blocker, pending: self.others_in_order(blocker) }
+            }
+
+            (Phase::ChallengeCounter { action, blocker, mut pending }, Move::Pass) => {
+                pending.remove(0);
+                Phase::ChallengeCounter { action, blocker, pending }
+            }
+            (Phase::ChallengeCounter { action, blocker, pending }, Move::Challenge) => {
+                let challenger = pending[0];
+                let honest = counters_for_action(&action, &self.config.reformation)
+                    .iter()
+                    .any(|card| self.players[blocker].cards.contains(card));
+                if honest {
+                    // the block stands (the engine doesn't swap the shown card here)
+                    Phase::LoseCard { player: challenger, then: Box::new(Phase::EndTurn) }
+                } else {
+                    Phase::LoseCard { player: blocker, then: Box::new(Phase::Perform { action }) }
+                }
+            }
+
+            (Phase::Swap, Move::Return(cards)) => {
+                for card in cards.iter() {
+                    if let Some(index) = self.players[actor].cards.iter().position(|c| c == card) {
+                        self.players[actor].cards.remove(index);
+                        self.deck.push(*card);
+                    }
+                }
+                self.deck.shuffle(rng);
+                Phase::EndTurn
+            }
+            (Phase::Exchange, Move::ReturnOne(card)) => {
+                if let Some(index) = self.players[actor].cards.iter().position(|c| c == card) {
+                    self.players[actor].cards.remove(index);
+                    self.deck.push(*card);
+                }
+                self.deck.shuffle(rng);
+                Phase::EndTurn
+            }
+
+            (Phase::LoseCard { player, then }, Move::Lose(card)) => {
+                if let Some(index) = self.players[player].cards.iter().position(|c| c == card) {
+                    self.discard(player, index);
+                }
+                *then
+            }
+
+            // a move that doesn't fit the phase, nothing happens
+            (phase, _) => phase,
+        };
+    }
+
+    // run all the steps nobody has to decide anything for until we hit the
+    // next real decision (or the game is over)
+    fn settle(&mut self) {
+        let actor = self.turn;
+
+        while !self.is_terminal() {
+            let phase = std::mem::replace(&mut self.phase, Phase::EndTurn);
+
+            self.phase = match phase {
+                Phase::ChallengeAction { action, mut pending } => {
+                    pending.retain(|index| self.players[*index].is_alive());
+                    if pending.is_empty() {
+                        Phase::AfterChallenge { action }
+                    } else {
+                        self.phase = Phase::ChallengeAction { action, pending };
+                        return;
+                    }
+                }
+                Phase::Counter { action, mut pending } => {
+                    pending.retain(|index| self.players[*index].is_alive());
+                    if pending.is_empty() {
+                        Phase::Perform { action }
+                    } else {
+                        self.phase = Phase::Counter { action, pending };
+                        return;
+                    }
+                }
+                Phase::ChallengeCounter { action, blocker, mut pending } => {
+                    pending.retain(|index| self.players[*index].is_alive());
+                    if pending.is_empty() {
+                        // nobody called the block so it stands
+                        Phase::EndTurn
+                    } else {
+                        self.phase = Phase::ChallengeCounter { action, blocker, pending };
+                        return;
+                    }
+                }
+                Phase::LoseCard { player, then } => {
+                    let cards = &self.players[player].cards;
+                    if cards.is_empty() {
+                        *then
+                    } else if cards.iter().all(|c| *c == cards[0]) {
+                        // no choice to make
+                        self.discard(player, 0);
+                        *then
+                    } else {
+                        self.phase = Phase::LoseCard { player, then };
+                        return;
+                    }
+                }
+                // foreign aid can be blocked by anyone, the rest only by the target
+                Phase::AfterChallenge { action } => {
+                    if counters_for_action(&action, &self.config.reformation).is_empty() {
+                        Phase::Perform { action }
+                    } else {
+                        let pending = match self.target_of(&action) {
+                            Some(target) => vec![target],
+                            None => self.others_in_order(actor),
+                        };
+                        Phase::Counter { action, pending }
+                    }
+                }
+                Phase::Perform { action } => self.perform(actor, &action),
+                Phase::EndTurn => {
+                    self.moves += 1;
+                    self.advance_turn();
+                    Phase::Turn
+                }
+                other @ (Phase::Turn | Phase::Swap | Phase::Exchange) => {
+                    self.phase = other;
+                    return;
+                }
+            };
+        }
+    }
+
+    // coins come out of the treasury and go back in just like in the engine
+    fn take(&mut self, player: usize, coins: u8) {
+        let coins = coins.min(self.treasury);
+        self.treasury -= coins;
+        self.players[player].coins += coins;
+    }
+
+    fn pay(&mut self, player: usize, cost: u8) {
+        let cost = cost.min(self.players[player].coins);
+        self.players[player].coins -= cost;
+        self.treasury = self.treasury.saturating_add(cost);
+    }
+
+    // a player who lost their last card gives their coins back to the treasury
+    fn discard(&mut self, player: usize, index: usize) {
+        let card = self.players[player].cards.remove(index);
+        self.discard_pile.push(card);
+        if !self.players[player].is_alive() {
+            self.pay(player, self.players[player].coins);
+        }
+    }
+
+    fn perform(&mut self, actor: usize, action: &Action) -> Phase {
+        let target = self.target_of(action);
+        match action {
+            Action::Income => self.take(actor, 1),
+            Action::ForeignAid => self.take(actor, 2),
+            Action::Tax => self.take(actor, self.config.tax_amount),
+            Action::Stealing(_) => {
+                if let Some(target) = target {
+                    let booty = self.players[target].coins.min(self.config.stealing_amount);
+                    self.players[target].coins -= booty;
+                    self.players[actor].coins += booty;
+                }
+            }
+            Action::Assassination(_) => {
+                self.pay(actor, self.config.assassination_cost);
+                if let Some(target) = target {
+                    return Phase::LoseCard { player: target, then: Box::new(Phase::EndTurn) };
+                }
+            }
+            // the inquisitor draws one, the actor decides which one goes back
+            Action::Swapping if self.config.reformation.inquisitor => {
+                if let Some(card) = self.deck.pop() {
+                    self.players[actor].cards.push(card);
+                    return Phase::Exchange;
+                }
+            }
+            Action::Swapping => {
+                // draw two, the actor decides which two go back
+                let drawn = (0..2).filter_map(|_| self.deck.pop()).collect::<Vec<Card>>();
+                if drawn.len() == 2 {
+                    self.players[actor].cards.extend(drawn);
+                    return Phase::Swap;
+                }
+                self.deck.extend(drawn);
+            }
+            Action::Conversion(_) => {
+                if let Some(target) = target {
+                    let cost = self.conversion_cost(actor, target);
+                    if self.players[actor].coins >= cost {
+                        self.players[actor].coins -= cost;
+                        self.treasury_reserve += cost;
+                        self.players[target].allegiance = self.players[target].allegiance.map(Allegiance::other);
+                    }
+                }
+            }
+            Action::Embezzlement => {
+                self.players[actor].coins += self.treasury_reserve;
+                self.treasury_reserve = 0;
+            }
+            // we don't know what the inquisitor gets to see so only the challenge counts
+            Action::Coup(_) | Action::Examine(_) => {}
+        }
+        Phase::EndTurn
+    }
+
+    fn advance_turn(&mut self) {
+        if let Some(next) = self.others_in_order(self.turn).first() {
+            self.turn = *next;
+        }
     }
 
     pub fn is_terminal(&self) -> bool {
-        self.opponents.is_empty()
+        self.alive_count() <= 1 || self.moves >= self.config.max_moves
     }
-    // reward function for rollouts
-    // rn mostly placeholder since we don’t simulate full elimination yet
-    pub fn reward(&self) -> f32 {
-        if self.is_terminal() {
-            1.0
-        } else {
-            0.0
+
+    // 1.0 for a win and 0.0 for a loss
+    // if the game isn't over yet we guess by everyones share of the cards left
+    pub fn reward(&self, player: usize) -> f32 {
+        let alive = self.alive_count();
+        if !self.players[player].is_alive() {
+            return 0.0;
         }
+        if alive == 1 {
+            return 1.0;
+        }
+
+        let total = self.players.iter().map(|p| p.cards.len()).sum::<usize>() as f32;
+        self.players[player].cards.len() as f32 / total
+    }
+
+    pub fn rewards(&self) -> Vec<f32> {
+        (0..self.players.len()).map(|player| self.reward(player)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::OtherBot;
+
+    fn player(name: &str, cards: Vec<Card>, coins: u8) -> SimPlayer {
+        SimPlayer { name: name.to_string(), cards, coins, allegiance: None }
+    }
+
+    fn state(players: Vec<SimPlayer>) -> SimState {
+        SimState {
+            me: 0,
+            players,
+            turn: 0,
+            deck: vec![Card::Duke, Card::Contessa, Card::Ambassador],
+            discard_pile: vec![],
+            moves: 0,
+            phase: Phase::Turn,
+            copies: 3,
+            treasury: 50,
+            treasury_reserve: 0,
+            config: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_legal_actions() {
+        let sim = state(vec![
+            player("me", vec![Card::Duke, Card::Captain], 7),
+            player("a", vec![Card::Duke, Card::Captain], 0),
+            player("b", vec![], 3),
+        ]);
+        let actions = sim.legal_actions();
+
+        assert!(actions.contains(&Action::Assassination("a".to_string())));
+        assert!(actions.contains(&Action::Coup("a".to_string())));
+        // nothing to steal from a and b is out
+        assert!(!actions.contains(&Action::Stealing("a".to_string())));
+        assert!(!actions.iter().any(|a| *a == Action::Coup("b".to_string())));
+
+        let mut rich = sim.clone();
+        rich.players[0].coins = 10;
+        assert_eq!(rich.legal_actions(), vec![Action::Coup("a".to_string())]);
+    }
+
+    #[test]
+    fn test_apply_action() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let sim = state(vec![
+            player("me", vec![Card::Duke, Card::Captain], 7),
+            player("a", vec![Card::Contessa], 4),
+        ]);
+
+        let next = sim.apply_action(&Action::Stealing("a".to_string()), &mut rng);
+        assert_eq!((next.players[0].coins, next.players[1].coins), (9, 2));
+        assert_eq!(next.turn, 1);
+
+        let next = sim.apply_action(&Action::Coup("a".to_string()), &mut rng);
+        assert!(next.is_terminal());
+        assert_eq!(next.rewards(), vec![1.0, 0.0]);
+        assert_eq!(next.discard_pile, vec![Card::Contessa]);
+
+        // the contessa blocks the assassination but we still keep our coins
+        let next = sim.apply_action(&Action::Assassination("a".to_string()), &mut rng);
+        assert_eq!(next.players[1].cards, vec![Card::Contessa]);
+        assert_eq!(next.players[0].coins, 7);
+    }
+
+    #[test]
+    fn test_challenge_bluff() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut sim = state(vec![
+            player("me", vec![Card::Contessa, Card::Captain], 2),
+            player("a", vec![Card::Duke, Card::Duke], 2),
+        ]);
+        sim.discard_pile = vec![Card::Duke];
+
+        // a can see all three dukes so our tax is a bluff they will call
+        let next = sim.apply_action(&Action::Tax, &mut rng);
+        assert_eq!(next.players[0].coins, 2);
+        assert_eq!(next.players[0].cards, vec![Card::Captain]);
+    }
+
+    #[test]
+    fn test_decisions() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let sim = state(vec![
+            player("me", vec![Card::Contessa, Card::Captain], 2),
+            player("a", vec![Card::Duke, Card::Assassin], 2),
+            player("b", vec![Card::Ambassador], 2),
+        ]);
+
+        // everyone after us gets asked in turn order
+        let next = sim.apply_move(&Move::Act(Action::Tax), &mut rng);
+        assert_eq!(next.to_move(), 1);
+        assert_eq!(next.legal_moves(), vec![Move::Pass, Move::Challenge]);
+        let next = next.apply_move(&Move::Pass, &mut rng);
+        assert_eq!(next.to_move(), 2);
+
+        // b calls our bluff and we get to pick what to lose
+        let next = next.apply_move(&Move::Challenge, &mut rng);
+        assert_eq!(next.to_move(), 0);
+        assert_eq!(next.legal_moves(), vec![Move::Lose(Card::Contessa), Move::Lose(Card::Captain)]);
+        let next = next.apply_move(&Move::Lose(Card::Contessa), &mut rng);
+        assert_eq!(next.players[0].cards, vec![Card::Captain]);
+        assert_eq!((next.phase.clone(), next.turn, next.players[0].coins), (Phase::Turn, 1, 2));
+
+        // a blocks our foreign aid with a real duke and b calls it
+        let next = sim.apply_move(&Move::Act(Action::ForeignAid), &mut rng);
+        assert_eq!(next.legal_moves(), vec![Move::Pass, Move::Counter]);
+        let next = next.apply_move(&Move::Counter, &mut rng);
+        assert_eq!(next.to_move(), 2);
+        let next = next.apply_move(&Move::Challenge, &mut rng);
+        assert!(next.is_terminal() || next.phase == Phase::Turn);
+        assert!(!next.players[2].is_alive());
+        assert_eq!(next.players[0].coins, 2);
+
+        // after swapping we pick which two go back
+        let mut swap = sim.apply_move(&Move::Act(Action::Swapping), &mut rng);
+        swap = swap.apply_move(&Move::Pass, &mut rng).apply_move(&Move::Pass, &mut rng);
+        assert_eq!(swap.phase, Phase::Swap);
+        assert_eq!(swap.players[0].cards.len(), 4);
+        let back = swap.default_move(&mut rng);
+        let next = swap.apply_move(&back, &mut rng);
+        assert_eq!(next.players[0].cards.len(), 2);
+        assert_eq!(next.deck.len(), 3);
+    }
+
+    #[test]
+    fn test_max_moves_from_context() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let bot = |name: &str| OtherBot { name: name.to_string(), coins: 2, cards: 2, allegiance: None };
+        let context = Context {
+            name: "me".to_string(),
+            cards: vec![Card::Duke, Card::Captain],
+            coins: 2,
+            playing_bots: vec![bot("me"), bot("a")],
+            discard_pile: vec![],
+            history: vec![],
+            moves: 1_000,
+            score: vec![],
+            config: GameConfig::default(),
+            treasury: 46,
+            treasury_reserve: 0,
+            copies: 3,
+        };
+
+        // the real game is in its last move so this turn is the last one
+        let sim = SimState::from_context(&context, &mut rng);
+        assert!(!sim.is_terminal());
+        assert!(sim.apply_action(&Action::Income, &mut rng).is_terminal());
+    }
+
+    #[test]
+    fn test_treasury() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut sim = state(vec![
+            player("me", vec![Card::Duke, Card::Captain], 7),
+            player("a", vec![Card::Contessa], 3),
+        ]);
+        sim.treasury = 1;
+
+        // income takes the last coin, after that income and tax get nothing
+        let next = sim.apply_action(&Action::Income, &mut rng);
+        assert_eq!((next.players[0].coins, next.treasury), (8, 0));
+        let next = next.apply_action(&Action::Income, &mut rng);
+        assert_eq!((next.players[1].coins, next.treasury), (3, 0));
+        let next = next.apply_action(&Action::Tax, &mut rng);
+        assert_eq!((next.players[0].coins, next.treasury), (8, 0));
+
+        // the coup is paid into the treasury and a gives its coins back
+        let next = sim.apply_action(&Action::Coup("a".to_string()), &mut rng);
+        assert_eq!((next.players[0].coins, next.players[1].coins), (0, 0));
+        assert_eq!(next.treasury, 11);
+    }
+
+    #[test]
+    fn test_factions() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut sim = state(vec![
+            player("me", vec![Card::Contessa, Card::Captain], 7),
+            player("a", vec![Card::Assassin, Card::Captain], 2),
+            player("b", vec![Card::Assassin, Card::Duke], 2),
+        ]);
+        sim.config.reformation.factions = true;
+        sim.players[0].allegiance = Some(Allegiance::Loyalist);
+        sim.players[1].allegiance = Some(Allegiance::Loyalist);
+        sim.players[2].allegiance = Some(Allegiance::Reformist);
+
+        // a is on our side so we can only go after b
+        let actions = sim.legal_actions();
+        assert!(actions.contains(&Action::Coup("b".to_string())));
+        assert!(!actions.contains(&Action::Coup("a".to_string())));
+        assert!(!actions.contains(&Action::Stealing("a".to_string())));
+        assert!(actions.contains(&Action::Conversion("me".to_string())));
+        assert!(actions.contains(&Action::Conversion("a".to_string())));
+        assert!(!actions.contains(&Action::Embezzlement));
+
+        // converting a pays into the reserve and makes a fair game
+        let next = sim.apply_action(&Action::Conversion("a".to_string()), &mut rng);
+        assert_eq!((next.players[0].coins, next.treasury_reserve), (5, 2));
+        assert_eq!(next.players[1].allegiance, Some(Allegiance::Reformist));
+        let mut next = next;
+        next.turn = 0;
+        assert!(next.legal_actions().contains(&Action::Stealing("a".to_string())));
+
+        // everyone on one side can target each other again
+        let mut same = sim.clone();
+        same.players[2].allegiance = Some(Allegiance::Loyalist);
+        assert!(same.legal_actions().contains(&Action::Coup("a".to_string())));
+
+        // we don't have the duke so a challenge of our embezzlement fails
+        sim.treasury_reserve = 3;
+        let embezzle = sim.apply_move(&Move::Act(Action::Embezzlement), &mut rng);
+        let next = embezzle.apply_move(&Move::Challenge, &mut rng);
+        assert!(matches!(next.phase, Phase::LoseCard { player: 1, .. }));
+        let next = next.apply_move(&Move::Lose(Card::Assassin), &mut rng);
+        assert_eq!((next.players[0].coins, next.treasury_reserve), (10, 0));
+        assert_eq!(next.players[0].cards.len(), 2);
+
+        // b does have the duke and gets nothing
+        sim.turn = 2;
+        let embezzle = sim.apply_move(&Move::Act(Action::Embezzlement), &mut rng);
+        let next = embezzle.apply_move(&Move::Challenge, &mut rng);
+        assert!(matches!(next.phase, Phase::LoseCard { player: 2, .. }));
+        let next = next.apply_move(&Move::Lose(Card::Assassin), &mut rng);
+        assert_eq!((next.players[2].coins, next.treasury_reserve), (2, 3));
+    }
+
+    #[test]
+    fn test_inquisitor() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut sim = state(vec![
+            player("me", vec![Card::Inquisitor, Card::Captain], 2),
+            player("a", vec![Card::Contessa, Card::Duke], 2),
+            player("b", vec![Card::Inquisitor, Card::Assassin], 2),
+        ]);
+        sim.config.reformation.inquisitor = true;
+        sim.deck = vec![Card::Duke, Card::Contessa, Card::Inquisitor];
+
+        // swapping with a real inquisitor isn't a bluff
+        let next = sim.apply_move(&Move::Act(Action::Swapping), &mut rng);
+        let next = next.apply_move(&Move::Pass, &mut rng).apply_move(&Move::Challenge, &mut rng);
+        assert_eq!(next.to_move(), 2);
+        assert!(matches!(next.phase, Phase::LoseCard { player: 2, .. }));
+
+        // the inquisitor only draws one card and gives one back
+        let exchange = sim.apply_move(&Move::Act(Action::Swapping), &mut rng);
+        let exchange = exchange.apply_move(&Move::Pass, &mut rng).apply_move(&Move::Pass, &mut rng);
+        assert_eq!(exchange.phase, Phase::Exchange);
+        assert_eq!(exchange.players[0].cards.len(), 3);
+        assert_eq!(exchange.deck.len(), 2);
+        assert!(exchange.legal_moves().contains(&Move::ReturnOne(Card::Captain)));
+        let next = exchange.apply_move(&Move::ReturnOne(Card::Captain), &mut rng);
+        assert_eq!(next.players[0].cards.len(), 2);
+        assert!(!next.players[0].cards.contains(&Card::Captain));
+        assert_eq!(next.deck.len(), 3);
+
+        // b blocks stealing with the inquisitor it really has
+        let steal = sim.apply_move(&Move::Act(Action::Stealing("b".to_string())), &mut rng);
+        let steal = steal.apply_move(&Move::Pass, &mut rng).apply_move(&Move::Pass, &mut rng);
+        assert_eq!(steal.to_move(), 2);
+        assert_eq!(steal.default_move(&mut rng), Move::Counter);
     }
 }
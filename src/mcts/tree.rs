@@ -1,96 +1,655 @@
-// this is our basic MCTS engine
-// currently using rollout evaluation
-// not full UCT tree expansion yet (skeleton is here though)
+// this is our MCTS engine (UCT)
+// every iteration does the 4 classic steps:
+//   1. selection     - walk down the tree picking children with UCB1
+//   2. expansion     - add one child for an action we haven't tried yet
+//   3. simulation    - random rollout from that new child
+//   4. backprop      - push the rewards back up to the root
+// nodes live in one Vec and point at each other by index so we don't
+// have to fight the borrow checker with a recursive tree
+//
+// the sim is random (shuffles, hidden cards) so nodes don't store a state,
+// instead every iteration replays the actions from the root state ("open loop")
+// and a node only keeps the move that leads to it and its stats
+//
+// moves aren't only actions: challenges, counters, swaps and card loss are
+// decisions in the tree too (see Phase in sim_state.rs)
+//
+// with a Determinizer every iteration also starts from a fresh deal of the
+// hidden cards (see mcts/determinize.rs), so the stats end up averaged over
+// all the hands the other bots could have
+//
+// with more than one thread every thread grows its own tree from the same
+// root and we add up the stats of the root moves at the end (root parallel)
+// nothing is shared while searching so there are no locks
+
+use std::thread;
+use std::time::{Duration, Instant};
 
 use rand::prelude::*;
-use super::sim_state::SimState;
-use crate::Action;
+use serde::Serialize;
+use super::determinize::Determinizer;
+use super::rollout::{policy, HeuristicPolicy, RolloutPolicy, RolloutPolicyFactory};
+use super::sim_state::{Move, SimState};
+
+// sqrt(2) is the textbook value for rewards between 0 and 1
+pub const DEFAULT_EXPLORATION: f32 = std::f32::consts::SQRT_2;
 
+// everything you can tune about a search
+// more iterations / time = stronger but slower
 #[derive(Clone)]
+pub struct MctsConfig {
+    // stop after this many iterations (use usize::MAX for time only)
+    pub iterations: usize,
+    // stop once this much time has passed, whatever comes first
+    pub time_budget: Option<Duration>,
+    // how many turns a rollout plays before we score the position as is
+    pub rollout_depth: usize,
+    // bigger exploration = try more of the less promising moves
+    pub exploration: f32,
+    // how the moves in a rollout get picked (see mcts/rollout.rs)
+    pub rollout_policy: RolloutPolicyFactory,
+    // how many threads search at the same time, the iterations are split
+    // between them (a Tournament already uses every core so this is 1 by default)
+    pub threads: usize,
+    // keep the tree of the last decision and carry on with it (see Mcts::reroot)
+    pub reuse_tree: bool,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 200,
+            time_budget: None,
+            rollout_depth: 50,
+            exploration: DEFAULT_EXPLORATION,
+            rollout_policy: policy(|| HeuristicPolicy),
+            threads: 1,
+            reuse_tree: true,
+        }
+    }
+}
+
+impl std::fmt::Debug for MctsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MctsConfig")
+            .field("iterations", &self.iterations)
+            .field("time_budget", &self.time_budget)
+            .field("rollout_depth", &self.rollout_depth)
+            .field("exploration", &self.exploration)
+            .field("threads", &self.threads)
+            .field("reuse_tree", &self.reuse_tree)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Debug)]
 struct Node {
-    state: SimState,
+    // the move that got us from the parent to this node (None for the root)
+    action: Option<Move>,
+    // the player who made that move, the value is from their point of view
+    player: usize,
+    parent: Option<usize>,
+    children: Vec<usize>,
     visits: u32,
     value: f32,
-    children: Vec<Node>,
-    action: Option<Action>,
 }
 
 impl Node {
-    fn new(state: SimState) -> Self {
+    fn new(action: Option<Move>, player: usize, parent: Option<usize>) -> Self {
         Self {
-            state,
+            action,
+            player,
+            parent,
+            children: vec![],
             visits: 0,
             value: 0.0,
-            children: vec![],
-            action: None,
         }
     }
+
+    fn mean(&self) -> f32 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.value / self.visits as f32
+        }
+    }
+}
+
+// what the search thinks of one move at the root
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MoveStats {
+    #[serde(rename = "move")]
+    pub mv: Move,
+    // the name of the player making the move
+    pub player: String,
+    pub visits: u32,
+    // average reward for that player, between 0 (always lost) and 1 (always won)
+    pub mean: f32,
+    // the true mean is within mean +/- confidence 95% of the time
+    // (normal approximation, so don't trust it with only a few visits)
+    pub confidence: f32,
+}
+
+// one node in a json dump of the tree
+#[derive(Serialize)]
+struct NodeDump {
+    #[serde(rename = "move")]
+    mv: Option<Move>,
+    player: String,
+    visits: u32,
+    mean: f32,
+    children: Vec<NodeDump>,
 }
 
+#[derive(Clone, Debug)]
 pub struct Mcts {
-    root: Node,
+    root_state: SimState,
+    nodes: Vec<Node>,
+    config: MctsConfig,
+    determinizer: Option<Determinizer>,
 }
 
 impl Mcts {
     pub fn new(state: SimState) -> Self {
+        Self::with_config(state, MctsConfig::default())
+    }
+
+    pub fn with_config(state: SimState, config: MctsConfig) -> Self {
+        let me = state.to_move();
         Self {
-            root: Node::new(state),
+            root_state: state,
+            nodes: vec![Node::new(None, me, None)],
+            config,
+            determinizer: None,
+        }
+    }
+
+    // resample the hidden cards before every iteration
+    pub fn with_determinizer(mut self, determinizer: Determinizer) -> Self {
+        self.determinizer = Some(determinizer);
+        self
+    }
+
+    // search until we run out of iterations or time (see MctsConfig)
+    pub fn search(&mut self) {
+        let deadline = self.config.time_budget.map(|budget| Instant::now() + budget);
+        let threads = self.config.threads.max(1);
+        let iterations = self.config.iterations;
+
+        if threads == 1 {
+            self.run(iterations, deadline);
+            return;
+        }
+
+        // every thread gets an empty tree with the same root and its share of the iterations
+        let trees = thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|worker| {
+                    let share = iterations / threads + usize::from(worker < iterations % threads);
+                    let mut tree = Mcts {
+                        root_state: self.root_state.clone(),
+                        nodes: vec![self.nodes[0].clone()],
+                        config: self.config.clone(),
+                        determinizer: self.determinizer.clone(),
+                    };
+                    tree.nodes[0].children.clear();
+                    tree.nodes[0].visits = 0;
+                    tree.nodes[0].value = 0.0;
+
+                    scope.spawn(move || {
+                        tree.run(share, deadline);
+                        tree
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("an MCTS thread panicked"))
+                .collect::<Vec<Mcts>>()
+        });
+
+        for tree in trees.iter() {
+            self.merge_root(tree);
+        }
+    }
+
+    // add the root stats of another tree to ours
+    // (only the root and its children, that's all best_move looks at)
+    fn merge_root(&mut self, other: &Mcts) {
+        self.nodes[0].visits += other.nodes[0].visits;
+        self.nodes[0].value += other.nodes[0].value;
+
+        for child in other.nodes[0].children.iter() {
+            let theirs = &other.nodes[*child];
+            let ours = self.nodes[0]
+                .children
+                .iter()
+                .copied()
+                .find(|index| self.nodes[*index].action == theirs.action);
+
+            match ours {
+                Some(index) => {
+                    self.nodes[index].visits += theirs.visits;
+                    self.nodes[index].value += theirs.value;
+                }
+                None => {
+                    let index = self.nodes.len();
+                    let mut node = Node::new(theirs.action.clone(), theirs.player, Some(0));
+                    node.visits = theirs.visits;
+                    node.value = theirs.value;
+                    self.nodes.push(node);
+                    self.nodes[0].children.push(index);
+                }
+            }
         }
     }
-    // run multiple simulations to estimate action quality
-    // each iteration performs a random rollout
-    pub fn search(&mut self, iterations: usize) {
+
+    fn run(&mut self, iterations: usize, deadline: Option<Instant>) {
         let mut rng = thread_rng();
+        let mut policy = (self.config.rollout_policy)();
 
         for _ in 0..iterations {
-            let reward = self.rollout(self.root.state.clone(), &mut rng);
-            self.root.visits += 1;
-            self.root.value += reward;
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+            let (node, state) = self.select_and_expand(&mut rng);
+            let rewards = self.rollout(state, policy.as_mut(), &mut rng);
+            self.backpropagate(node, &rewards);
+        }
+    }
+
+    // walk down with UCB1 and add one new child as soon as we find a move
+    // we haven't tried from that node yet
+    fn select_and_expand(&mut self, rng: &mut ThreadRng) -> (usize, SimState) {
+        let mut index = 0;
+        let mut state = match &self.determinizer {
+            Some(determinizer) => determinizer.sample(&self.root_state, rng),
+            None => self.root_state.clone(),
+        };
+
+        while !state.is_terminal() {
+            let legal = state.legal_moves();
+            let untried = legal
+                .iter()
+                .filter(|action| !self.nodes[index].children.iter().any(|c| self.nodes[*c].action.as_ref() == Some(*action)))
+                .cloned()
+                .collect::<Vec<Move>>();
+
+            if let Some(action) = untried.choose(rng) {
+                let child = self.nodes.len();
+                self.nodes.push(Node::new(Some(action.clone()), state.to_move(), Some(index)));
+                self.nodes[index].children.push(child);
+                return (child, state.apply_move(action, rng));
+            }
+
+            // only children that are still legal in this playout count
+            let child = self.best_child(index, &legal);
+            state = state.apply_move(self.nodes[child].action.as_ref().unwrap(), rng);
+            index = child;
+        }
+
+        (index, state)
+    }
+
+    // UCB1: mean reward + c * sqrt(ln(parent visits) / child visits)
+    fn best_child(&self, index: usize, legal: &[Move]) -> usize {
+        let parent_visits = (self.nodes[index].visits.max(1) as f32).ln();
+
+        *self.nodes[index]
+            .children
+            .iter()
+            .filter(|child| legal.iter().any(|a| self.nodes[**child].action.as_ref() == Some(a)))
+            .max_by(|a, b| {
+                self.ucb1(**a, parent_visits)
+                    .partial_cmp(&self.ucb1(**b, parent_visits))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap()
+    }
+
+    fn ucb1(&self, index: usize, parent_visits_ln: f32) -> f32 {
+        let node = &self.nodes[index];
+        if node.visits == 0 {
+            return f32::INFINITY;
+        }
+        node.mean() + self.config.exploration * (parent_visits_ln / node.visits as f32).sqrt()
+    }
+
+    // play until the game ends or we hit the depth limit
+    fn rollout(&self, mut state: SimState, policy: &mut dyn RolloutPolicy, rng: &mut ThreadRng) -> Vec<f32> {
+        let last_turn = state.moves + self.config.rollout_depth;
+        while !state.is_terminal() && state.moves < last_turn {
+            let mv = policy.choose(&state, rng);
+            state = state.apply_move(&mv, rng);
+        }
+
+        state.rewards()
+    }
+
+    // every node gets the reward of the player who picked its action
+    fn backpropagate(&mut self, mut index: usize, rewards: &[f32]) {
+        loop {
+            let node = &mut self.nodes[index];
+            node.visits += 1;
+            node.value += rewards[node.player];
+
+            match node.parent {
+                Some(parent) => index = parent,
+                None => break,
+            }
+        }
+    }
+
+    // the most visited action is the most robust pick
+    // (the best mean can be a fluke with very few visits)
+    pub fn best_move(&self) -> Option<Move> {
+        self.root_stats().into_iter().next().map(|stats| stats.mv)
+    }
+
+    fn stats(&self, index: usize) -> MoveStats {
+        let node = &self.nodes[index];
+        let mean = node.mean();
+        let confidence = if node.visits == 0 {
+            1.0
+        } else {
+            1.96 * (mean * (1.0 - mean) / node.visits as f32).max(0.0).sqrt()
+        };
+
+        MoveStats {
+            mv: node.action.clone().unwrap_or(Move::Pass),
+            player: self.root_state.players[node.player].name.clone(),
+            visits: node.visits,
+            mean,
+            confidence,
+        }
+    }
+
+    // every move at the root, most visited first (that's the order best_move picks)
+    pub fn root_stats(&self) -> Vec<MoveStats> {
+        let mut stats = self.nodes[0].children.iter().map(|child| self.stats(*child)).collect::<Vec<MoveStats>>();
+        stats.sort_by_key(|stats| std::cmp::Reverse(stats.visits));
+        stats
+    }
+
+    // the line of play the search expects: always follow the most visited child
+    pub fn principal_variation(&self) -> Vec<MoveStats> {
+        let mut line = vec![];
+        let mut index = 0;
+        // (min of Reverse picks the first one on a tie, just like root_stats)
+        while let Some(child) = self.nodes[index].children.iter().copied().min_by_key(|child| std::cmp::Reverse(self.nodes[*child].visits)) {
+            line.push(self.stats(child));
+            index = child;
+        }
+        line
+    }
+
+    fn label(&self, index: usize) -> String {
+        let node = &self.nodes[index];
+        let name = &self.root_state.players[node.player].name;
+        match &node.action {
+            Some(mv) => format!("{} {:?}", name, mv),
+            None => String::from("root"),
         }
     }
-    // simulate random future actions until depth limit
-    // this prevents infinite loops since our sim is incomplete
-    fn rollout(&self, mut state: SimState, rng: &mut ThreadRng) -> f32 {
-        for _ in 0..20 {   // limit depth to 20 steps
-            if state.is_terminal() {
-                return state.reward();
+
+    // the tree in graphviz dot format, only nodes up to `max_depth` below the root
+    // render it with: dot -Tsvg tree.dot > tree.svg
+    pub fn to_dot(&self, max_depth: usize) -> String {
+        let mut dot = String::from("digraph mcts {\n    node [shape=box];\n");
+        let mut stack = vec![(0, 0)];
+
+        while let Some((index, depth)) = stack.pop() {
+            let node = &self.nodes[index];
+            dot.push_str(&format!(
+                "    n{} [label=\"{}\\nvisits {}\\nmean {:.3}\"];\n",
+                index,
+                self.label(index).replace('"', "'"),
+                node.visits,
+                node.mean()
+            ));
+
+            if depth < max_depth {
+                for child in node.children.iter() {
+                    dot.push_str(&format!("    n{} -> n{};\n", index, child));
+                    stack.push((*child, depth + 1));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    // the tree as json, only nodes up to `max_depth` below the root
+    pub fn to_json(&self, max_depth: usize) -> String {
+        serde_json::to_string_pretty(&self.dump(0, max_depth)).unwrap_or_default()
+    }
+
+    fn dump(&self, index: usize, depth_left: usize) -> NodeDump {
+        let node = &self.nodes[index];
+        NodeDump {
+            mv: node.action.clone(),
+            player: self.root_state.players[node.player].name.clone(),
+            visits: node.visits,
+            mean: node.mean(),
+            children: if depth_left == 0 {
+                vec![]
+            } else {
+                node.children.iter().map(|child| self.dump(*child, depth_left - 1)).collect()
+            },
+        }
+    }
+
+    fn find_child(&self, index: usize, player: usize, mv: &Move) -> Option<usize> {
+        self.nodes[index]
+            .children
+            .iter()
+            .copied()
+            .find(|child| self.nodes[*child].player == player && self.nodes[*child].action.as_ref() == Some(mv))
+    }
+
+    // keep the work of an earlier search for the next decision
+    // we walk down the moves we saw in the real game since then (by player name)
+    // and the node we end up at becomes the root of a new tree for `state`,
+    // everything else gets dropped
+    // passes aren't in the history so we walk through them whenever they fit
+    // and card losses with no choice have no node so we skip them
+    // returns None if the game went somewhere the tree doesn't know
+    pub fn reroot(&self, observed: &[(String, Move)], state: SimState) -> Option<Mcts> {
+        let mut index = 0;
+
+        for (name, mv) in observed {
+            let player = self.root_state.player_index(name)?;
+            loop {
+                if let Some(child) = self.find_child(index, player, mv) {
+                    index = child;
+                    break;
+                }
+
+                // someone before them didn't challenge or counter
+                // (if it was their own pass they must have done something else)
+                let pass = self.nodes[index].children.iter().copied().find(|child| {
+                    self.nodes[*child].action == Some(Move::Pass)
+                        && (self.nodes[*child].player != player || matches!(mv, Move::Act(_) | Move::Lose(_)))
+                });
+                match (pass, mv) {
+                    (Some(pass), _) => index = pass,
+                    (None, Move::Lose(_)) => break,
+                    (None, _) => return None,
+                }
             }
-    
-            let actions = state.legal_actions();
-            if actions.is_empty() {
-                return 0.0;
+        }
+
+        // the players between the last thing we saw and us passed too
+        let me = &state.players[state.to_move()].name;
+        let me_before = self.root_state.player_index(me)?;
+        let legal = state.legal_moves();
+        loop {
+            let children = &self.nodes[index].children;
+            if children.iter().any(|child| {
+                let node = &self.nodes[*child];
+                node.player == me_before && node.action.as_ref().is_some_and(|mv| legal.contains(mv))
+            }) {
+                break;
             }
-    
-            let action = actions[rng.gen_range(0..actions.len())].clone();
-            state = state.apply_action(&action);
-        }
-    
-        0.0 // return neutral if depth limit reached
-    }
-    // choose the action that performed best in simulations
-    // rn very simple evaluation logic
-    pub fn best_action(&self) -> Option<Action> {
-        let actions = self.root.state.legal_actions();
-    
-        let mut best_score = f32::MIN;
-        let mut best_action = None;
-    
-        let mut rng = rand::thread_rng();
-    
-        for action in actions {
-            let mut total = 0.0;
-    
-            for _ in 0..50 {
-                let next_state = self.root.state.apply_action(&action);
-                total += self.rollout(next_state.clone(), &mut rng);
+            match children.iter().copied().find(|child| self.nodes[*child].action == Some(Move::Pass)) {
+                Some(pass) => index = pass,
+                None => break,
             }
-    
-            if total > best_score {
-                best_score = total;
-                best_action = Some(action);
+        }
+        if self.nodes[index].children.iter().any(|child| self.nodes[*child].player != me_before) {
+            return None;
+        }
+
+        // copy the subtree over, players get the index they have in the new state
+        // (dead players are gone from the context) and moves by players who
+        // aren't there anymore get dropped
+        let mut tree = Mcts::with_config(state, self.config.clone());
+        tree.nodes[0].visits = self.nodes[index].visits;
+        tree.nodes[0].value = self.nodes[index].value;
+
+        let mut stack = vec![(index, 0)];
+        while let Some((old, new)) = stack.pop() {
+            for child in self.nodes[old].children.iter() {
+                let node = &self.nodes[*child];
+                let name = &self.root_state.players[node.player].name;
+                let Some(player) = tree.root_state.player_index(name) else {
+                    continue;
+                };
+
+                let copy = tree.nodes.len();
+                tree.nodes.push(Node {
+                    action: node.action.clone(),
+                    player,
+                    parent: Some(new),
+                    children: vec![],
+                    visits: node.visits,
+                    value: node.value,
+                });
+                tree.nodes[new].children.push(copy);
+                stack.push((*child, copy));
             }
         }
-    
-        best_action
+
+        Some(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcts::rollout::RandomPolicy;
+    use crate::mcts::sim_state::{Phase, SimPlayer};
+    use crate::{Action, Card};
+
+    fn state() -> SimState {
+        let player = |name: &str, cards: Vec<Card>| SimPlayer { name: name.to_string(), cards, coins: 2, allegiance: None };
+        SimState {
+            me: 0,
+            players: vec![player("me", vec![Card::Duke, Card::Captain]), player("a", vec![Card::Contessa, Card::Assassin])],
+            turn: 0,
+            deck: vec![Card::Duke, Card::Ambassador, Card::Ambassador, Card::Captain],
+            discard_pile: vec![],
+            moves: 0,
+            phase: Phase::Turn,
+            copies: 3,
+            treasury: 50,
+            treasury_reserve: 0,
+            config: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_budget() {
+        let mut mcts = Mcts::with_config(state(), MctsConfig { iterations: 30, ..Default::default() });
+        mcts.search();
+        assert_eq!(mcts.nodes[0].visits, 30);
+        assert!(mcts.best_move().is_some());
+
+        // out of time before the first iteration
+        let mut mcts = Mcts::with_config(
+            state(),
+            MctsConfig { iterations: usize::MAX, time_budget: Some(Duration::ZERO), ..Default::default() },
+        );
+        mcts.search();
+        assert_eq!(mcts.nodes[0].visits, 0);
+
+        let mut mcts = Mcts::with_config(
+            state(),
+            MctsConfig { rollout_depth: 0, rollout_policy: policy(|| RandomPolicy), ..Default::default() },
+        );
+        mcts.search();
+        assert_eq!(mcts.nodes[0].visits, 200);
+    }
+
+    #[test]
+    fn test_threads() {
+        let mut mcts = Mcts::with_config(state(), MctsConfig { iterations: 101, threads: 4, ..Default::default() });
+        mcts.search();
+
+        // the root moves add up to the iterations of all threads
+        assert_eq!(mcts.nodes[0].visits, 101);
+        let children = &mcts.nodes[0].children;
+        assert_eq!(children.iter().map(|c| mcts.nodes[*c].visits).sum::<u32>(), 101);
+        let mut moves = children.iter().map(|c| format!("{:?}", mcts.nodes[*c].action)).collect::<Vec<String>>();
+        moves.sort();
+        moves.dedup();
+        assert_eq!(moves.len(), children.len());
+        assert!(mcts.best_move().is_some());
+    }
+
+    #[test]
+    fn test_reroot() {
+        let mut mcts = Mcts::with_config(state(), MctsConfig { iterations: 300, ..Default::default() });
+        mcts.search();
+
+        // we took income and it's a's turn now
+        let income = mcts.find_child(0, 0, &Move::Act(Action::Income)).unwrap();
+        let mut next = state();
+        next.me = 0;
+        next.turn = 1;
+        next.players[0].coins = 3;
+        let observed = vec![("me".to_string(), Move::Act(Action::Income))];
+        let tree = mcts.reroot(&observed, next.clone()).unwrap();
+        assert_eq!(tree.nodes[0].visits, mcts.nodes[income].visits);
+        assert!(tree.nodes.iter().skip(1).all(|node| node.parent.is_some()));
+        assert!(tree.nodes[0].children.iter().all(|child| tree.nodes[*child].player == 1));
+
+        // nobody challenged our tax so now a gets to act
+        let observed = vec![("me".to_string(), Move::Act(Action::Tax))];
+        let tree = mcts.reroot(&observed, next.clone()).unwrap();
+        assert!(tree.nodes[0].children.iter().all(|child| {
+            matches!(tree.nodes[*child].action, Some(Move::Act(_))) && tree.nodes[*child].player == 1
+        }));
+
+        // nobody in the tree has taken that action
+        let observed = vec![("a".to_string(), Move::Act(Action::Tax))];
+        assert!(mcts.reroot(&observed, next).is_none());
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut mcts = Mcts::with_config(state(), MctsConfig { iterations: 100, ..Default::default() });
+        mcts.search();
+
+        let stats = mcts.root_stats();
+        assert_eq!(stats.iter().map(|s| s.visits).sum::<u32>(), 100);
+        assert_eq!(Some(stats[0].mv.clone()), mcts.best_move());
+        assert!(stats.windows(2).all(|pair| pair[0].visits >= pair[1].visits));
+        assert!(stats.iter().all(|s| s.player == "me" && (0.0..=1.0).contains(&s.mean) && s.confidence >= 0.0));
+
+        let line = mcts.principal_variation();
+        assert_eq!(line[0], stats[0]);
+        assert!(line.windows(2).all(|pair| pair[0].visits >= pair[1].visits));
+
+        let dot = mcts.to_dot(1);
+        assert!(dot.starts_with("digraph mcts {"));
+        assert_eq!(dot.matches(" -> ").count(), stats.len());
+
+        let json: serde_json::Value = serde_json::from_str(&mcts.to_json(1)).unwrap();
+        assert_eq!(json["visits"], 100);
+        assert_eq!(json["children"].as_array().unwrap().len(), stats.len());
+        assert!(json["children"][0]["children"].as_array().unwrap().is_empty());
     }
 }
@@ -0,0 +1,273 @@
+//! Step through recorded games in the terminal.
+//!
+//! A replay is built from the [LogEvent] written by the
+//! [crate::game_log::JsonLinesSink] and renders each move of a game the same
+//! way the engine does while playing it.
+//!
+//! ```rust,no_run
+//! use coup::replay::Replay;
+//! use std::{fs::File, io::BufReader};
+//!
+//! let file = BufReader::new(File::open("games.jsonl").unwrap());
+//! let replay = Replay::from_reader(file).unwrap();
+//! replay.run(std::io::stdin().lock(), std::io::stdout()).unwrap();
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+use crate::{
+	describe_event,
+	game_log::{read_json_lines, LogEvent, Seat},
+	History,
+};
+
+/// A single move of a recorded game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+	/// The number of the game within the recording
+	pub round: u64,
+	/// The seed the game was played with
+	pub seed: u64,
+	/// The number of this move within its game starting at 1.
+	/// The setup of a game is move 0.
+	pub step: usize,
+	/// The state of the table after this move
+	pub seats: Vec<Seat>,
+	/// The log lines of this move
+	pub lines: Vec<String>,
+}
+
+/// A recorded game (or many) you can step through.
+#[derive(Debug, Clone, Default)]
+pub struct Replay {
+	frames: Vec<Frame>,
+}
+
+impl Replay {
+	/// Build a replay from the events of one or more games.
+	pub fn new(events: &[LogEvent]) -> Self {
+		let mut frames = Vec::new();
+		let mut round = 0;
+		let mut seed = 0;
+		let mut step = 0;
+		let mut seats: Vec<Seat> = Vec::new();
+		let mut history: Vec<History> = Vec::new();
+		let mut lines: Vec<String> = Vec::new();
+
+		for event in events {
+			match event {
+				LogEvent::Setup {
+					round: setup_round,
+					seed: setup_seed,
+					seats: setup_seats,
+				} => {
+					round = *setup_round;
+					seed = *setup_seed;
+					step = 0;
+					seats.clone_from(setup_seats);
+					history.clear();
+					frames.push(Frame {
+						round,
+						seed,
+						step,
+						seats: seats.clone(),
+						lines: vec![format!(
+							"🤺  This rounds player:\n     {}\n\n 🎲  Seed: \x1b[90m{}\x1b[39m\n",
+							seats
+								.iter()
+								.map(|seat| seat.to_string())
+								.collect::<Vec<String>>()
+								.join("\n     "),
+							seed
+						)],
+					});
+				},
+				LogEvent::Event { event } => {
+					// Cards are the only thing we can track between state changes
+					if let History::LoseInfluence { by, card } = event {
+						if let Some(seat) = seats.iter_mut().find(|seat| seat.name == *by) {
+							if let Some(index) = seat.cards.iter().position(|c| c == card) {
+								seat.cards.remove(index);
+							}
+						}
+					}
+					history.push(event.clone());
+					if let Some(line) = describe_event(&history, |name| {
+						seats.iter().find(|seat| seat.name == name).cloned().unwrap_or(
+							Seat {
+								name: String::from(name),
+								coins: 0,
+								cards: vec![],
+								allegiance: None,
+							},
+						)
+					}) {
+						lines.push(line);
+					}
+				},
+				LogEvent::State {
+					seats: state_seats, ..
+				} => {
+					step += 1;
+					seats.clone_from(state_seats);
+					frames.push(Frame {
+						round,
+						seed,
+						step,
+						seats: seats.clone(),
+						lines: std::mem::take(&mut lines),
+					});
+				},
+				LogEvent::Result { winners, moves, .. } => {
+					lines.push(format!(
+						"\n 🎉🎉🎉 The winner{} \x1b[1m{}\x1b[0m \x1b[90min {} moves\x1b[39m\n",
+						if winners.len() > 1 { "s are" } else { " is" },
+						winners.join(" and "),
+						moves
+					));
+					if let Some(frame) = frames.last_mut() {
+						frame.lines.append(&mut lines);
+					}
+				},
+			}
+		}
+
+		Self { frames }
+	}
+
+	/// Read a replay from a JSON Lines game log.
+	pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+		Ok(Self::new(&read_json_lines(reader)?))
+	}
+
+	/// All moves of all games in this replay.
+	pub fn frames(&self) -> &[Frame] {
+		&self.frames
+	}
+
+	/// Render a move with the table as it was after this move and the log of
+	/// the game up to this move.
+	pub fn render(&self, index: usize) -> String {
+		let Some(frame) = self.frames.get(index) else {
+			return String::new();
+		};
+
+		let moves = self
+			.frames
+			.iter()
+			.filter(|other| other.round == frame.round && other.seed == frame.seed)
+			.count()
+			- 1;
+		let log = self.frames[..=index]
+			.iter()
+			.filter(|other| other.round == frame.round && other.seed == frame.seed)
+			.flat_map(|other| other.lines.iter())
+			.flat_map(|line| line.lines())
+			.collect::<Vec<&str>>();
+
+		let mut output = format!(
+			" 🎬  Game \x1b[36m{}\x1b[39m  move \x1b[36m{}\x1b[39m/{}  \x1b[90mseed {}\x1b[39m\n\n",
+			frame.round + 1,
+			frame.step,
+			moves,
+			frame.seed
+		);
+		for seat in frame.seats.iter() {
+			output.push_str(&format!("     {}\n", seat));
+		}
+		output.push('\n');
+		for line in log.iter().skip(log.len().saturating_sub(20)) {
+			output.push_str(&format!(" {}\n", line));
+		}
+		output
+	}
+
+	/// Step through the replay by reading commands from the input:
+	/// - `enter` or `n` to go to the next move
+	/// - `p` to go to the previous move
+	/// - a number to jump to that move of the current game
+	/// - `q` to quit
+	pub fn run<R: BufRead, W: Write>(
+		&self,
+		input: R,
+		mut output: W,
+	) -> io::Result<()> {
+		if self.frames.is_empty() {
+			writeln!(output, " Nothing to replay")?;
+			return Ok(());
+		}
+
+		let mut index = 0;
+		let mut lines = input.lines();
+		loop {
+			write!(output, "\x1b[2J\x1b[H{}", self.render(index))?;
+			write!(
+				output,
+				"\n \x1b[90m[enter] next  [p] previous  [number] jump to move  [q] quit\x1b[39m\n > "
+			)?;
+			output.flush()?;
+
+			let Some(command) = lines.next() else {
+				return Ok(());
+			};
+			match command?.trim() {
+				"" | "n" => index = (index + 1).min(self.frames.len() - 1),
+				"p" => index = index.saturating_sub(1),
+				"q" => return Ok(()),
+				step => {
+					if let Ok(step) = step.parse::<usize>() {
+						let frame = &self.frames[index];
+						if let Some(position) = self.frames.iter().position(|other| {
+							other.round == frame.round
+								&& other.seed == frame.seed
+								&& other.step == step
+						}) {
+							index = position;
+						}
+					}
+				},
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		bots::{HonestBot, StaticBot},
+		game_log::MemorySink,
+		Coup,
+	};
+
+	#[test]
+	fn test_replay() {
+		let sink = MemorySink::new();
+		let mut coup = Coup::new_with_seed(
+			vec![
+				Box::new(HonestBot),
+				Box::new(HonestBot),
+				Box::new(StaticBot),
+			],
+			11,
+		);
+		coup.log = false;
+		coup.add_log_sink(Box::new(sink.clone()));
+		coup.play();
+
+		let events = sink.events();
+		let replay = Replay::new(&events);
+
+		assert_eq!(replay.frames().len(), coup.moves + 1);
+		assert_eq!(replay.frames()[0].step, 0);
+		assert_eq!(replay.frames().last().unwrap().step, coup.moves);
+		assert_eq!(replay.frames().last().unwrap().seats, coup.get_seats());
+		assert!(replay.render(coup.moves).contains("The winner"));
+
+		let mut output = Vec::new();
+		replay.run("n\nn\np\nq\n".as_bytes(), &mut output).unwrap();
+		assert!(String::from_utf8(output)
+			.unwrap()
+			.contains("move \x1b[36m1\x1b[39m/"));
+	}
+}
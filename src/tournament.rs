@@ -0,0 +1,241 @@
+//! Play a lot of games across multiple threads.
+//!
+//! Each worker thread builds its own bots from the [BotFactory] you pass in
+//! and plays its share of the games with its own [Coup] instance.
+//! The scores of all workers are summed up by bot name at the end.
+//!
+//! ```rust
+//! use coup::{
+//!     bot::factory,
+//!     bots::{HonestBot, StaticBot},
+//!     tournament::Tournament,
+//! };
+//!
+//! let tournament =
+//!     Tournament::new(vec![factory(|| StaticBot), factory(|| HonestBot)])
+//!         .with_seed(42)
+//!         .with_threads(2);
+//! let score = tournament.run(100);
+//! assert_eq!(score.len(), 2);
+//! ```
+
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use std::{
+	sync::atomic::{AtomicU64, Ordering},
+	thread,
+	time::Duration,
+};
+
+use crate::{
+	bot::BotFactory, bots::BotRegistry, Coup, GameConfig, Reformation, Score,
+};
+
+/// A tournament of many games played in parallel.
+pub struct Tournament {
+	// Each factory with whether it builds a fresh bot for each game
+	bots: Vec<(BotFactory, bool)>,
+	seed: u64,
+	threads: usize,
+	config: GameConfig,
+}
+
+impl Tournament {
+	/// Create a new tournament with a factory for each bot that takes part.
+	/// By default we use a random seed and one thread per available core.
+	pub fn new(factories: Vec<BotFactory>) -> Self {
+		Self {
+			bots: factories.into_iter().map(|factory| (factory, false)).collect(),
+			seed: thread_rng().gen(),
+			threads: thread::available_parallelism().map_or(1, |n| n.get()),
+			config: GameConfig::default(),
+		}
+	}
+
+	/// Create a new tournament with the bots you look up by name in the
+	/// [BotRegistry].
+	pub fn from_registry(
+		registry: &BotRegistry,
+		names: &[&str],
+	) -> Result<Self, String> {
+		let mut tournament = Self::new(vec![]);
+		for name in names {
+			let entry = registry
+				.get(name)
+				.ok_or(format!("There is no bot called \"{}\"", name))?;
+			tournament.bots.push((entry.factory.clone(), entry.fresh_per_game));
+		}
+		Ok(tournament)
+	}
+
+	/// Set the seed all games are derived from.
+	/// With the same bots, seed and amount of threads the tournament plays the
+	/// exact same games as long as your bots don't bring their own randomness.
+	pub fn with_seed(mut self, seed: u64) -> Self {
+		self.seed = seed;
+		self
+	}
+
+	/// Set the amount of worker threads to play games on.
+	pub fn with_threads(mut self, threads: usize) -> Self {
+		self.threads = threads.max(1);
+		self
+	}
+
+	/// Play all games with the numbers and rules of this config, see
+	/// [Coup::set_config].
+	pub fn with_config(mut self, config: GameConfig) -> Self {
+		self.config = config;
+		self
+	}
+
+	/// Play all games with the rules of the Reformation expansion you turned on.
+	pub fn with_reformation(mut self, reformation: Reformation) -> Self {
+		self.config.reformation = reformation;
+		self
+	}
+
+	/// Seat up to this many bots at the table for each game, see
+	/// [Coup::set_table_size].
+	pub fn with_table_size(mut self, table_size: usize) -> Self {
+		self.config.table_size = table_size;
+		self
+	}
+
+	/// The seed this tournament plays with.
+	pub fn get_seed(&self) -> u64 {
+		self.seed
+	}
+
+	/// Play n number of rounds quietly and return the summed up score sorted
+	/// from best to worst.
+	pub fn run(&self, rounds: u64) -> Score {
+		self.play(rounds, |_| {})
+	}
+
+	/// Play n number of rounds and show the progress and the score in the CLI.
+	pub fn looping(&self, rounds: u64) -> Score {
+		println!(
+			" Starting \x1b[36m{}\x1b[39m rounds on \x1b[36m{}\x1b[39m threads with seed \x1b[36m{}\x1b[39m\x1b[?25l",
+			Coup::format_number_with_separator(rounds),
+			self.threads,
+			self.seed
+		);
+
+		let score = self.play(rounds, |done| {
+			let percentage = ((done as f64 / rounds.max(1) as f64) * 100.0).round();
+			print!("\r\x1b[2K {:>3}% done", percentage);
+			let _ = std::io::Write::flush(&mut std::io::stdout());
+		});
+
+		println!("\r\x1b[2K ╔═════════════════╗\n ║ 🎲🎲 \x1b[1mBOARD\x1b[0m 🎲🎲 ║\n ╚═════════════════╝\x1b[?25h");
+		score.iter().for_each(|(name, score)| {
+			let percentage = if rounds > 0 {
+				format!("{:.3}", (score * 100.0) / rounds as f64)
+			} else {
+				String::from("0")
+			};
+			println!(
+				"\x1b[90m {:>8}%\x1b[39m  \x1b[31m{:>15.5}\x1b[39m  \x1b[33m{}\x1b[39m",
+				percentage, score, name
+			);
+		});
+		if let Some((name, _)) = score.first() {
+			println!("\n 🎉🎉🎉 The winner is: \x1b[1m{}\x1b[0m\n", name);
+		}
+
+		score
+	}
+
+	fn play(&self, rounds: u64, progress: impl Fn(u64)) -> Score {
+		let done = &AtomicU64::new(0);
+		let threads = (self.threads as u64).clamp(1, rounds.max(1));
+		let mut seed_rng = StdRng::seed_from_u64(self.seed);
+
+		// Each worker gets its own seed and an even share of the rounds
+		let workers = (0..threads)
+			.map(|worker| {
+				let share = rounds / threads + u64::from(worker < rounds % threads);
+				(seed_rng.gen::<u64>(), share)
+			})
+			.collect::<Vec<(u64, u64)>>();
+
+		let scores = thread::scope(|scope| {
+			let handles = workers
+				.into_iter()
+				.map(|(seed, share)| {
+					scope.spawn(move || {
+						let bots = self
+							.bots
+							.iter()
+							.map(|(factory, fresh)| {
+								(factory(), fresh.then(|| factory.clone()))
+							})
+							.collect();
+						let mut coup = Coup::new_with_factories(bots, seed);
+						coup.set_quiet(true);
+						coup.set_config(self.config);
+						coup.rounds = share;
+						for round in 0..share {
+							coup.play();
+							coup.round = round + 1;
+							done.fetch_add(1, Ordering::Relaxed);
+						}
+						coup.score
+					})
+				})
+				.collect::<Vec<_>>();
+
+			while handles.iter().any(|handle| !handle.is_finished()) {
+				progress(done.load(Ordering::Relaxed));
+				thread::sleep(Duration::from_millis(100));
+			}
+			progress(done.load(Ordering::Relaxed));
+
+			handles
+				.into_iter()
+				.map(|handle| handle.join().unwrap())
+				.collect::<Vec<Score>>()
+		});
+
+		let mut score: Score = Vec::new();
+		for (name, points) in scores.into_iter().flatten() {
+			match score.iter_mut().find(|(existing, _)| *existing == name) {
+				Some((_, total)) => *total += points,
+				None => score.push((name, points)),
+			}
+		}
+		score.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+		score
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		bot::factory,
+		bots::{HonestBot, StaticBot},
+	};
+
+	#[test]
+	fn test_tournament() {
+		let tournament = Tournament::new(vec![
+			factory(|| StaticBot),
+			factory(|| HonestBot),
+			factory(|| StaticBot),
+		])
+		.with_seed(7)
+		.with_threads(3);
+
+		let score = tournament.run(100);
+		let mut names =
+			score.iter().map(|(name, _)| name.clone()).collect::<Vec<String>>();
+		names.sort();
+		assert_eq!(names, vec!["HonestBot", "StaticBot", "StaticBot 2"]);
+		assert!(score.iter().map(|(_, score)| score).sum::<f64>().abs() < 1e-9);
+		assert!(score[0].1 >= score[1].1 && score[1].1 >= score[2].1);
+
+		// The same seed and threads play the same games
+		assert_eq!(tournament.run(100), score);
+	}
+}